@@ -0,0 +1,32 @@
+use std::error::Error;
+
+use clap::Parser;
+use tokio::net::TcpListener;
+use tron_sdk_rs::signer::{LocalSigner, Signer, remote};
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// Listen address
+    #[arg(short, long, default_value = "127.0.0.1:8090")]
+    listen: String,
+
+    /// Tron account private key(hex format)
+    #[arg(env = "TRON_PRIVATE_KEY", hide = true)]
+    private_key: String,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+
+    let signer = LocalSigner::from_hex(&args.private_key)?;
+    let listener = TcpListener::bind(&args.listen).await?;
+    println!(
+        "remote signer for {} listening on http://{}",
+        signer.address(),
+        listener.local_addr()?
+    );
+
+    remote::serve(listener, signer).await?;
+    Ok(())
+}
@@ -2,10 +2,9 @@ use std::{error::Error, str::FromStr};
 
 use clap::Parser;
 use num_bigint::BigInt;
-use tonic::Request;
 use tron_sdk_rs::{
-    client::{Address, GrpcClient},
-    utils::crypto,
+    client::GrpcClient,
+    signer::{LocalSigner, Signer},
 };
 
 #[derive(Parser, Debug)]
@@ -43,34 +42,18 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let amount = BigInt::from_str(&args.amount)?;
     println!("transfer amount: {}", amount);
 
-    let sk = crypto::hex2sk(&args.private_key)?;
-
-    let address = Address::from_public_key(&sk.public_key(&secp256k1::Secp256k1::new()));
-
-    let from = address.to_bs58();
-    println!("from address: {}", from);
+    let signer = LocalSigner::from_hex(&args.private_key)?;
+    println!("from address: {}", signer.address());
 
     let mut cli = GrpcClient::new(&args.endpoint).await?;
-    let ext = cli
-        .trc20_transfer(&from, &args.to, &args.contract, amount, args.fee_limit)
-        .await?
-        .into_inner();
-    let mut tx = ext.transaction.ok_or("create transfer tx error")?;
-    tx.signature.push(crypto::sign_tx(ext.txid.clone(), &sk)?);
-
-    println!("tx hash: {}", hex::encode(ext.txid));
-
-    let res = cli
-        .client()
-        .broadcast_transaction(Request::new(tx))
-        .await?
-        .into_inner();
-    println!(
-        "send tx result: success={}, message={}",
-        res.result,
-        String::from_utf8(res.message)
-            .map_err(|e| format!("result message stringify err: {:?}", e))?,
-    );
+    let tx = cli
+        .signed_trc20_transfer(&signer, &args.to, &args.contract, amount, args.fee_limit)
+        .await?;
+    let raw = tx.raw_data.as_ref().ok_or("create transfer tx error")?;
+    println!("tx hash: {}", hex::encode(GrpcClient::get_tx_hash(raw)));
+
+    cli.broadcast_transaction(tx).await?;
+    println!("send tx success");
 
     Ok(())
 }
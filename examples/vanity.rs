@@ -0,0 +1,168 @@
+use std::{error::Error, time::Duration};
+
+use clap::{Args as ClapArgs, Parser, Subcommand};
+use tron_sdk_rs::{
+    client::Address,
+    wallet::{Kdf, Keystore, VanityProgress, VanitySearch},
+};
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Search for a matching address
+    Search {
+        #[command(flatten)]
+        pattern: Pattern,
+
+        /// Number of search threads, defaults to the available parallelism
+        #[arg(short, long)]
+        threads: Option<usize>,
+
+        /// Save the key as a keystore file instead of printing it
+        #[arg(short, long)]
+        keystore: Option<String>,
+
+        /// Keystore password
+        #[arg(long, env = "TRON_KEYSTORE_PASSWORD", hide_env_values = true)]
+        password: Option<String>,
+    },
+    /// Mine a CREATE2 salt for a matching contract address
+    Create2 {
+        #[command(flatten)]
+        pattern: Pattern,
+
+        /// Number of search threads, defaults to the available parallelism
+        #[arg(short, long)]
+        threads: Option<usize>,
+
+        /// Factory contract address
+        #[arg(short, long)]
+        deployer: String,
+
+        /// keccak256 of the init code(hex format)
+        #[arg(long)]
+        init_code_hash: String,
+    },
+    /// Show the difficulty of a pattern without searching
+    Estimate {
+        #[command(flatten)]
+        pattern: Pattern,
+    },
+}
+
+#[derive(ClapArgs, Debug)]
+struct Pattern {
+    /// Base58 prefix, matched after the leading `T`
+    #[arg(short, long, default_value = "")]
+    prefix: String,
+
+    /// Base58 suffix
+    #[arg(short, long, default_value = "")]
+    suffix: String,
+
+    /// Case-insensitive matching
+    #[arg(short = 'i', long)]
+    ignore_case: bool,
+}
+
+impl Pattern {
+    fn search(&self, threads: Option<usize>) -> VanitySearch {
+        let search = VanitySearch::new()
+            .prefix(&self.prefix)
+            .suffix(&self.suffix)
+            .case_insensitive(self.ignore_case);
+        match threads {
+            Some(threads) => search.threads(threads),
+            None => search,
+        }
+    }
+}
+
+fn report(p: &VanityProgress) {
+    let eta = p
+        .eta()
+        .map_or("-".to_string(), |d| format!("{}s", d.as_secs()));
+    eprintln!(
+        "{} attempts, {:.0} keys/s, {:.1}% chance so far, eta {}",
+        p.attempts,
+        p.rate(),
+        p.probability() * 100.0,
+        eta
+    );
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+
+    match args.command {
+        Command::Estimate { pattern } => {
+            let attempts = pattern.search(None).expected_attempts()?;
+            println!("expected attempts: {:.0}", attempts);
+        }
+        Command::Search {
+            pattern,
+            threads,
+            keystore,
+            password,
+        } => {
+            let search = pattern.search(threads);
+            if keystore.is_some() && password.is_none() {
+                return Err("keystore password is required".into());
+            }
+            println!("expected attempts: {:.0}", search.expected_attempts()?);
+
+            let found = search
+                .search_with_progress(Duration::from_secs(1), report)?
+                .ok_or("no match")?;
+            println!(
+                "address: {} ({} attempts in {:.1}s)",
+                found.address,
+                found.attempts,
+                found.elapsed.as_secs_f64()
+            );
+
+            match (keystore, password) {
+                (Some(path), Some(password)) => {
+                    Keystore::encrypt(&found.private_key, &password, Kdf::STANDARD)?.save(&path)?;
+                    println!("keystore saved to {}", path);
+                }
+                _ => println!("private key: {}", found.private_key.expose_hex().as_str()),
+            }
+        }
+        Command::Create2 {
+            pattern,
+            threads,
+            deployer,
+            init_code_hash,
+        } => {
+            let search = pattern.search(threads);
+            let deployer: Address = deployer.parse()?;
+            let mut code_hash = [0u8; 32];
+            hex::decode_to_slice(init_code_hash.trim_start_matches("0x"), &mut code_hash)?;
+            println!("expected attempts: {:.0}", search.expected_attempts()?);
+
+            let found = search
+                .search_create2_with_progress(
+                    &deployer,
+                    &code_hash,
+                    Duration::from_secs(1),
+                    report,
+                )?
+                .ok_or("no match")?;
+            println!(
+                "address: {} ({} attempts in {:.1}s)",
+                found.address,
+                found.attempts,
+                found.elapsed.as_secs_f64()
+            );
+            println!("salt: 0x{}", hex::encode(found.salt));
+        }
+    }
+
+    Ok(())
+}
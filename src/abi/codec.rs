@@ -0,0 +1,389 @@
+use num_bigint::{BigInt, BigUint, Sign};
+
+use crate::{
+    Error,
+    abi::{ParamType, Token},
+    client::Address,
+};
+
+const WORD: usize = 32;
+
+/// Encodes `tokens` as the parameters of `types`(the tuple encoding of call data and
+/// return data), checking every value against its type.
+pub fn encode(types: &[ParamType], tokens: &[Token]) -> Result<Vec<u8>, Error> {
+    if types.len() != tokens.len() {
+        return Err(Error::Abi(format!(
+            "abi encode: get {} values, expect {}",
+            tokens.len(),
+            types.len()
+        )));
+    }
+    for (i, (token, ty)) in tokens.iter().zip(types).enumerate() {
+        if !token.type_check(ty) {
+            return Err(Error::Abi(format!(
+                "abi encode: value {} is not a valid {}: {}",
+                i, ty, token
+            )));
+        }
+    }
+    let mut out = Vec::new();
+    encode_tuple(types, tokens, &mut out);
+    Ok(out)
+}
+
+/// Decodes the parameters of `types`. Dirty padding, out of range integers and offsets are
+/// rejected, `address` values are decoded with the `0x41` prefix.
+pub fn decode(types: &[ParamType], data: &[u8]) -> Result<Vec<Token>, Error> {
+    decode_tuple(types, data)
+}
+
+fn encode_tuple(types: &[ParamType], tokens: &[Token], out: &mut Vec<u8>) {
+    let head_len: usize = types.iter().map(|ty| ty.head_len()).sum();
+    let mut tail = Vec::new();
+    for (ty, token) in types.iter().zip(tokens) {
+        if ty.is_dynamic() {
+            out.extend_from_slice(&uint_word(&BigUint::from(head_len + tail.len())));
+            encode_token(ty, token, &mut tail);
+        } else {
+            encode_token(ty, token, out);
+        }
+    }
+    out.extend(tail);
+}
+
+// `token` is type checked
+fn encode_token(ty: &ParamType, token: &Token, out: &mut Vec<u8>) {
+    match (ty, token) {
+        (_, Token::Address(a)) => {
+            out.extend_from_slice(&[0; 12]);
+            out.extend_from_slice(&a.evm_bytes());
+        }
+        (_, Token::Bool(b)) => out.extend_from_slice(&uint_word(&BigUint::from(*b as u8))),
+        (_, Token::Uint(n) | Token::TrcToken(n)) => out.extend_from_slice(&uint_word(n)),
+        (_, Token::Int(n)) => out.extend_from_slice(&int_word(n)),
+        (_, Token::FixedBytes(b)) => out.extend(padded(b)),
+        (_, Token::Bytes(b)) => {
+            out.extend_from_slice(&uint_word(&BigUint::from(b.len())));
+            out.extend(padded(b));
+        }
+        (_, Token::String(s)) => {
+            out.extend_from_slice(&uint_word(&BigUint::from(s.len())));
+            out.extend(padded(s.as_bytes()));
+        }
+        (ParamType::Array(ty), Token::Array(tokens)) => {
+            out.extend_from_slice(&uint_word(&BigUint::from(tokens.len())));
+            encode_tuple(&vec![(**ty).clone(); tokens.len()], tokens, out);
+        }
+        (ParamType::FixedArray(ty, n), Token::FixedArray(tokens)) => {
+            encode_tuple(&vec![(**ty).clone(); *n], tokens, out);
+        }
+        (ParamType::Tuple(types), Token::Tuple(tokens)) => encode_tuple(types, tokens, out),
+        _ => unreachable!("token is type checked"),
+    }
+}
+
+fn uint_word(n: &BigUint) -> [u8; WORD] {
+    let bytes = n.to_bytes_be();
+    let mut word = [0; WORD];
+    word[WORD - bytes.len()..].copy_from_slice(&bytes);
+    word
+}
+
+// two's complement
+fn int_word(n: &BigInt) -> [u8; WORD] {
+    let mut word = match n.sign() {
+        Sign::Minus => [0xff; WORD],
+        _ => [0; WORD],
+    };
+    let bytes = n.to_signed_bytes_be();
+    word[WORD - bytes.len()..].copy_from_slice(&bytes);
+    word
+}
+
+fn padded(b: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    let pad = (WORD - b.len() % WORD) % WORD;
+    b.iter().copied().chain(std::iter::repeat_n(0, pad))
+}
+
+// `data` starts at the encoding of the tuple and may continue past it
+fn decode_tuple(types: &[ParamType], data: &[u8]) -> Result<Vec<Token>, Error> {
+    let mut offset = 0;
+    let mut tokens = Vec::with_capacity(types.len());
+    for ty in types {
+        let token = if ty.is_dynamic() {
+            let tail = read_usize(data, offset)?;
+            decode_token(ty, data.get(tail..).ok_or_else(|| out_of_range(tail))?)?
+        } else {
+            decode_token(ty, data.get(offset..).ok_or_else(|| out_of_range(offset))?)?
+        };
+        tokens.push(token);
+        offset += ty.head_len();
+    }
+    Ok(tokens)
+}
+
+fn decode_token(ty: &ParamType, data: &[u8]) -> Result<Token, Error> {
+    let token = match ty {
+        ParamType::Address => {
+            let word = read_word(data, 0)?;
+            check_padding(ty, &word[..12])?;
+            Token::Address(Address::from_bytes(&word[12..])?)
+        }
+        ParamType::Bool => match read_uint(ty, data, 8)? {
+            n if n == BigUint::ZERO => Token::Bool(false),
+            n if n == BigUint::from(1u8) => Token::Bool(true),
+            _ => {
+                return Err(Error::Abi(format!(
+                    "abi decode: invalid bool {}",
+                    hex(data)
+                )));
+            }
+        },
+        ParamType::Uint(bits) => Token::Uint(read_uint(ty, data, *bits)?),
+        ParamType::TrcToken => Token::TrcToken(read_uint(ty, data, 256)?),
+        ParamType::Int(_) => {
+            // not sign extended from the type bits if out of range
+            let token = Token::Int(BigInt::from_signed_bytes_be(read_word(data, 0)?));
+            if !token.type_check(ty) {
+                return Err(Error::Abi(format!(
+                    "abi decode: {} out of range: {}",
+                    ty, token
+                )));
+            }
+            token
+        }
+        ParamType::FixedBytes(n) => {
+            let word = read_word(data, 0)?;
+            check_padding(ty, &word[*n..])?;
+            Token::FixedBytes(word[..*n].to_vec())
+        }
+        ParamType::Bytes => Token::Bytes(read_bytes(ty, data)?),
+        ParamType::String => Token::String(
+            String::from_utf8(read_bytes(ty, data)?)
+                .map_err(|e| Error::Abi(format!("abi decode: invalid string: {}", e)))?,
+        ),
+        ParamType::Array(elem) => {
+            let len = read_usize(data, 0)?;
+            // every element takes at least one word, bounds the allocation
+            if len > data.len() / WORD {
+                return Err(out_of_range(len));
+            }
+            Token::Array(decode_tuple(&vec![(**elem).clone(); len], &data[WORD..])?)
+        }
+        ParamType::FixedArray(elem, n) => {
+            if *n > data.len() / WORD {
+                return Err(out_of_range(*n));
+            }
+            Token::FixedArray(decode_tuple(&vec![(**elem).clone(); *n], data)?)
+        }
+        ParamType::Tuple(types) => Token::Tuple(decode_tuple(types, data)?),
+    };
+    Ok(token)
+}
+
+fn read_word(data: &[u8], offset: usize) -> Result<&[u8; WORD], Error> {
+    offset
+        .checked_add(WORD)
+        .and_then(|end| data.get(offset..end))
+        .map(|w| w.try_into().expect("word length"))
+        .ok_or_else(|| out_of_range(offset))
+}
+
+fn read_uint(ty: &ParamType, data: &[u8], bits: usize) -> Result<BigUint, Error> {
+    let word = read_word(data, 0)?;
+    check_padding(ty, &word[..WORD - bits / 8])?;
+    Ok(BigUint::from_bytes_be(word))
+}
+
+// offsets and lengths
+fn read_usize(data: &[u8], offset: usize) -> Result<usize, Error> {
+    let word = read_word(data, offset)?;
+    let n = BigUint::from_bytes_be(word);
+    usize::try_from(&n).map_err(|_| Error::Abi(format!("abi decode: {} is out of range", n)))
+}
+
+fn read_bytes(ty: &ParamType, data: &[u8]) -> Result<Vec<u8>, Error> {
+    let len = read_usize(data, 0)?;
+    let padded_len = len.div_ceil(WORD) * WORD;
+    let body = data
+        .get(WORD..)
+        .and_then(|d| d.get(..padded_len))
+        .ok_or_else(|| out_of_range(len))?;
+    check_padding(ty, &body[len..])?;
+    Ok(body[..len].to_vec())
+}
+
+fn check_padding(ty: &ParamType, padding: &[u8]) -> Result<(), Error> {
+    if padding.iter().any(|b| *b != 0) {
+        return Err(Error::Abi(format!(
+            "abi decode: dirty padding of {}: {}",
+            ty,
+            hex(padding)
+        )));
+    }
+    Ok(())
+}
+
+fn out_of_range(n: usize) -> Error {
+    Error::Abi(format!(
+        "abi decode: offset or length {} is out of range",
+        n
+    ))
+}
+
+fn hex(data: &[u8]) -> String {
+    hex::encode(&data[..data.len().min(WORD)])
+}
+
+#[cfg(test)]
+mod test {
+    use num_bigint::{BigInt, BigUint};
+
+    use super::{decode, encode};
+    use crate::{
+        abi::{ParamType, Token},
+        client::Address,
+    };
+
+    fn types(s: &str) -> Vec<ParamType> {
+        match format!("({})", s).parse().unwrap() {
+            ParamType::Tuple(types) => types,
+            _ => unreachable!(),
+        }
+    }
+
+    fn words(s: &[&str]) -> Vec<u8> {
+        s.iter()
+            .flat_map(|w| hex::decode(format!("{:0>64}", w)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_spec_vectors() {
+        // https://docs.soliditylang.org/en/latest/abi-spec.html#examples
+        let ty = types("bytes,bool,uint256[]");
+        let tokens = vec![
+            Token::Bytes(b"dave".to_vec()),
+            Token::Bool(true),
+            Token::Array(vec![1u64.into(), 2u64.into(), 3u64.into()]),
+        ];
+        let data = words(&[
+            "60",
+            "1",
+            "a0",
+            "4",
+            "6461766500000000000000000000000000000000000000000000000000000000",
+            "3",
+            "1",
+            "2",
+            "3",
+        ]);
+        assert_eq!(encode(&ty, &tokens).unwrap(), data);
+        assert_eq!(decode(&ty, &data).unwrap(), tokens);
+
+        let ty = types("uint256,uint32[],bytes10,bytes");
+        let tokens = vec![
+            0x123u64.into(),
+            Token::Array(vec![0x456u64.into(), 0x789u64.into()]),
+            Token::FixedBytes(b"1234567890".to_vec()),
+            Token::Bytes(b"Hello, world!".to_vec()),
+        ];
+        let data = words(&[
+            "123",
+            "80",
+            "3132333435363738393000000000000000000000000000000000000000000000",
+            "e0",
+            "2",
+            "456",
+            "789",
+            "d",
+            "48656c6c6f2c20776f726c642100000000000000000000000000000000000000",
+        ]);
+        assert_eq!(encode(&ty, &tokens).unwrap(), data);
+        assert_eq!(decode(&ty, &data).unwrap(), tokens);
+
+        let ty = types("uint256[][],string[]");
+        let tokens = vec![
+            Token::Array(vec![
+                Token::Array(vec![1u64.into(), 2u64.into()]),
+                Token::Array(vec![3u64.into()]),
+            ]),
+            Token::Array(vec!["one".into(), "two".into(), "three".into()]),
+        ];
+        let data = words(&[
+            "40",
+            "140",
+            "2",
+            "40",
+            "a0",
+            "2",
+            "1",
+            "2",
+            "1",
+            "3",
+            "3",
+            "60",
+            "a0",
+            "e0",
+            "3",
+            "6f6e650000000000000000000000000000000000000000000000000000000000",
+            "3",
+            "74776f0000000000000000000000000000000000000000000000000000000000",
+            "5",
+            "7468726565000000000000000000000000000000000000000000000000000000",
+        ]);
+        assert_eq!(encode(&ty, &tokens).unwrap(), data);
+        assert_eq!(decode(&ty, &data).unwrap(), tokens);
+    }
+
+    #[test]
+    fn test_tron_types() {
+        let address: Address = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".parse().unwrap();
+        let ty = types("address,int8,trcToken,(bool,string)[2]");
+        let tokens = vec![
+            address.into(),
+            (-2i64).into(),
+            Token::TrcToken(1_000_001u64.into()),
+            Token::FixedArray(vec![
+                Token::Tuple(vec![true.into(), "a".into()]),
+                Token::Tuple(vec![false.into(), "".into()]),
+            ]),
+        ];
+        let data = encode(&ty, &tokens).unwrap();
+        assert_eq!(&data[12..32], &address.evm_bytes());
+        assert_eq!(
+            hex::encode(&data[32..64]),
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe"
+        );
+        let decoded = decode(&ty, &data).unwrap();
+        assert_eq!(decoded, tokens);
+        assert_eq!(decoded[0].to_string(), "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t");
+        assert_eq!(decoded[3].to_string(), r#"[(true, "a"), (false, "")]"#);
+    }
+
+    #[test]
+    fn test_invalid() {
+        // out of range values
+        let uint8 = types("uint8");
+        assert!(encode(&uint8, &[256u64.into()]).is_err());
+        assert!(encode(&uint8, &[255u64.into()]).is_ok());
+        let int8 = types("int8");
+        assert!(encode(&int8, &[(-129i64).into()]).is_err());
+        assert!(encode(&int8, &[(-128i64).into()]).is_ok());
+        let uint256 = types("uint256");
+        assert!(encode(&uint256, &[Token::Uint(BigUint::from(1u8) << 256)]).is_err());
+        assert!(encode(&types("bytes2"), &[Token::FixedBytes(vec![1])]).is_err());
+        assert!(encode(&uint256, &[Token::Int(BigInt::from(1))]).is_err());
+        assert!(encode(&uint256, &[]).is_err());
+
+        // dirty padding, truncated data and bad offsets
+        assert!(decode(&uint8, &words(&["100"])).is_err());
+        assert!(decode(&types("bool"), &words(&["2"])).is_err());
+        assert!(decode(&types("int8"), &words(&["80"])).is_err());
+        assert!(decode(&types("address"), &words(&["1".repeat(42).as_str()])).is_err());
+        assert!(decode(&uint256, &[0; 31]).is_err());
+        assert!(decode(&types("bytes"), &words(&["20", "21"])).is_err());
+        assert!(decode(&types("string"), &words(&["ffffffff"])).is_err());
+        assert!(decode(&types("uint256[]"), &words(&["20", "ffffffffffff"])).is_err());
+    }
+}
@@ -0,0 +1,719 @@
+//! Typed contract bindings generated from a JSON ABI, for build scripts.
+//!
+//! ```ignore
+//! // build.rs
+//! tron_sdk_rs::abi::codegen::configure().compile("abi/Token.json", "Token")?;
+//!
+//! // src/lib.rs, the bindings are in the `token` module
+//! include!(concat!(env!("OUT_DIR"), "/token.rs"));
+//! ```
+//!
+//! Every function becomes a method of the contract struct: `view` and `pure` functions are
+//! called with `trigger_constant_contract` and return the decoded outputs, other functions
+//! return the unsigned transaction. Every event becomes a struct decoded from a log.
+//!
+//! Types map to `Address`, `bool`, `u8` to `u128`(`BigUint` for other sizes), `i8` to
+//! `i128`(`BigInt` for other sizes), `[u8; N]`, `Vec<u8>`, `String`, `Vec<T>`, `[T; N]` and
+//! tuples.
+
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    Error,
+    abi::{AbiEntry, JsonAbi, ParamType, StateMutability},
+};
+
+/// A `Builder` with the default settings.
+pub fn configure() -> Builder {
+    Builder::default()
+}
+
+#[derive(Debug, Clone)]
+pub struct Builder {
+    sdk_path: String,
+    out_dir: Option<PathBuf>,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            sdk_path: "::tron_sdk_rs".to_string(),
+            out_dir: None,
+        }
+    }
+}
+
+impl Builder {
+    /// Path of this crate in the generated code, `::tron_sdk_rs` by default.
+    pub fn sdk_path(mut self, path: &str) -> Self {
+        self.sdk_path = path.to_string();
+        self
+    }
+
+    /// Defaults to the `OUT_DIR` of the build script.
+    pub fn out_dir(mut self, out_dir: impl AsRef<Path>) -> Self {
+        self.out_dir = Some(out_dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Generates the bindings of the ABI JSON file at `abi_path` into `<out_dir>/<name>.rs`,
+    /// with `name` in snake case, and returns the path of the generated file.
+    pub fn compile(&self, abi_path: impl AsRef<Path>, name: &str) -> Result<PathBuf, Error> {
+        let abi_path = abi_path.as_ref();
+        let json = fs::read_to_string(abi_path)
+            .map_err(|e| Error::Abi(format!("read {} err: {}", abi_path.display(), e)))?;
+        let code = self.generate(&JsonAbi::from_json(&json)?, name)?;
+
+        let out_dir = match (&self.out_dir, env::var_os("OUT_DIR")) {
+            (Some(out_dir), _) => out_dir.clone(),
+            (None, Some(out_dir)) => PathBuf::from(out_dir),
+            (None, None) => {
+                return Err(Error::Abi(
+                    "OUT_DIR is not set, configure out_dir".to_string(),
+                ));
+            }
+        };
+        let out = out_dir.join(format!("{}.rs", snake_case(name)));
+        fs::write(&out, code)
+            .map_err(|e| Error::Abi(format!("write {} err: {}", out.display(), e)))?;
+        if env::var_os("OUT_DIR").is_some() {
+            println!("cargo:rerun-if-changed={}", abi_path.display());
+        }
+        Ok(out)
+    }
+
+    /// The bindings of `abi`: a module named `name` in snake case, with the contract struct
+    /// `name` and one `<Event>Event` struct per event.
+    pub fn generate(&self, abi: &JsonAbi, name: &str) -> Result<String, Error> {
+        if !is_type_name(name) {
+            return Err(Error::Abi(format!("invalid contract name: {}", name)));
+        }
+        let mut w = Writer::default();
+        w.line(&format!(
+            "// Generated by tron_sdk_rs::abi::codegen from the {} ABI, do not edit.",
+            name
+        ));
+        w.open(&format!("pub mod {} {{", snake_case(name)));
+        w.line("#![allow(clippy::all)]");
+        w.line("");
+        w.line(&format!("use {} as sdk;", self.sdk_path));
+        w.line("");
+        w.line(&format!("/// Bindings of the `{}` contract.", name));
+        w.line("#[derive(Debug, Clone, Copy, PartialEq, Eq)]");
+        w.open(&format!("pub struct {} {{", name));
+        w.line("pub address: sdk::client::Address,");
+        w.close("}");
+        w.line("");
+        w.open(&format!("impl {} {{", name));
+        w.open("pub fn new(address: sdk::client::Address) -> Self {");
+        w.line("Self { address }");
+        w.close("}");
+        let mut names = Names::new(&["new"]);
+        for entry in abi.functions() {
+            w.line("");
+            write_function(&mut w, entry, &names.next(&snake_case(&entry.name)))?;
+        }
+        w.close("}");
+
+        let mut names = Names::new(&[]);
+        for entry in abi.events() {
+            w.line("");
+            // overloads are numbered before the case conversion, `Transfer1Event`
+            let name = format!(
+                "{}Event",
+                pascal_case(&names.next(&snake_case(&entry.name)))
+            );
+            write_event(&mut w, entry, &name)?;
+        }
+
+        w.line("");
+        w.open(
+            "fn take(values: &mut impl Iterator<Item = sdk::abi::Token>) -> Result<sdk::abi::Token, sdk::Error> {",
+        );
+        w.line("values.next().ok_or_else(|| sdk::Error::Abi(\"missing abi value\".to_string()))");
+        w.close("}");
+        w.line("");
+        w.open("fn unexpected(token: sdk::abi::Token) -> sdk::Error {");
+        w.line("sdk::Error::Abi(format!(\"unexpected abi value: {}\", token))");
+        w.close("}");
+        w.close("}");
+        Ok(w.out)
+    }
+}
+
+// names used by the generated method bodies
+const LOCALS: &[&str] = &[
+    "client",
+    "function",
+    "data",
+    "ext",
+    "result",
+    "values",
+    "take",
+    "unexpected",
+    "sdk",
+];
+
+fn write_function(w: &mut Writer, entry: &AbiEntry, name: &str) -> Result<(), Error> {
+    let function = entry.function()?;
+    let payable = entry.mutability() == StateMutability::Payable;
+    let mut params = match entry.is_constant() {
+        true => Names::new(LOCALS),
+        false => Names::new(&[LOCALS, &["owner", "call_value", "fee_limit"]].concat()),
+    };
+    let args = entry
+        .inputs
+        .iter()
+        .zip(&function.inputs)
+        .enumerate()
+        .map(|(i, (p, ty))| (params.next(&param_name(&p.name, i)), ty))
+        .collect::<Vec<_>>();
+    let arg_list = args
+        .iter()
+        .map(|(name, ty)| format!(", {}: {}", name, rust_type(ty)))
+        .collect::<String>();
+    let tokens = args
+        .iter()
+        .map(|(name, ty)| to_token(ty, name, 0))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    w.line(&format!("/// `{}`", function));
+    if entry.is_constant() {
+        let ret = match function.outputs.as_slice() {
+            [ty] => rust_type(ty),
+            types => tuple_type(types),
+        };
+        w.open(&format!(
+            "pub async fn {}(&self, client: &mut sdk::client::GrpcClient{}) -> Result<{}, sdk::Error> {{",
+            name, arg_list, ret
+        ));
+    } else {
+        let call_value = match payable {
+            true => {
+                w.line("///");
+                w.line("/// `call_value` is the TRX in sun sent with the call.");
+                ", call_value: i64"
+            }
+            false => "",
+        };
+        w.open(&format!(
+            "pub async fn {}(&self, client: &mut sdk::client::GrpcClient, owner: &sdk::client::Address{}{}, fee_limit: i64) -> Result<sdk::api::TransactionExtention, sdk::Error> {{",
+            name, arg_list, call_value
+        ));
+    }
+    w.line(&format!(
+        "let function: sdk::abi::Function = \"{}\".parse()?;",
+        function
+    ));
+    w.line(&format!(
+        "let data = function.encode_input(&[{}])?;",
+        tokens
+    ));
+
+    if entry.is_constant() {
+        w.open("let ext = client");
+        w.line(".contract_call(None, &self.address.to_bs58(), data, 0, None)");
+        w.line(".await?");
+        w.line(".into_inner();");
+        w.close("");
+        write_return_check(w);
+        if function.outputs.is_empty() {
+            w.line("Ok(())");
+        } else {
+            w.open("let result = ext.constant_result.first().ok_or_else(|| {");
+            w.line("sdk::Error::UnexpectedResponse(\"constant result is empty\".to_string())");
+            w.close("})?;");
+            w.line("let mut values = function.decode_output(result)?.into_iter();");
+            match function.outputs.as_slice() {
+                [ty] => w.line(&from_token(ty, "take(&mut values)?", 0)),
+                types => w.line(&format!(
+                    "Ok({})",
+                    tuple(
+                        types
+                            .iter()
+                            .map(|ty| format!("{}?", from_token(ty, "take(&mut values)?", 0)))
+                    )
+                )),
+            }
+        }
+    } else {
+        w.open("let ext = client");
+        w.line(&format!(
+            ".contract_call(Some(&owner.to_bs58()), &self.address.to_bs58(), data, {}, Some(fee_limit))",
+            match payable {
+                true => "call_value",
+                false => "0",
+            }
+        ));
+        w.line(".await?");
+        w.line(".into_inner();");
+        w.close("");
+        write_return_check(w);
+        w.line("Ok(ext)");
+    }
+    w.close("}");
+    Ok(())
+}
+
+fn write_return_check(w: &mut Writer) {
+    w.open("if let Some(e) = ext.result.as_ref().and_then(sdk::Error::from_return) {");
+    w.line("return Err(e);");
+    w.close("}");
+}
+
+fn write_event(w: &mut Writer, entry: &AbiEntry, name: &str) -> Result<(), Error> {
+    let event = entry.event()?;
+    let mut fields = Names::new(&[]);
+    let fields = entry
+        .inputs
+        .iter()
+        .zip(&event.inputs)
+        .enumerate()
+        .map(|(i, (p, e))| {
+            // indexed reference values are decoded as their hash
+            let ty = match e.kind {
+                ParamType::Bytes
+                | ParamType::String
+                | ParamType::Array(_)
+                | ParamType::FixedArray(..)
+                | ParamType::Tuple(_)
+                    if e.indexed =>
+                {
+                    ParamType::FixedBytes(32)
+                }
+                _ => e.kind.clone(),
+            };
+            (fields.next(&param_name(&p.name, i)), ty)
+        })
+        .collect::<Vec<_>>();
+
+    w.line(&format!("/// `{}`", event));
+    w.line("#[derive(Debug, Clone, PartialEq, Eq)]");
+    if fields.is_empty() {
+        w.line(&format!("pub struct {} {{}}", name));
+    } else {
+        w.open(&format!("pub struct {} {{", name));
+        for (field, ty) in &fields {
+            w.line(&format!("pub {}: {},", field, rust_type(ty)));
+        }
+        w.close("}");
+    }
+    w.line("");
+    w.open(&format!("impl {} {{", name));
+    w.line(&format!(
+        "pub const SIGNATURE: &'static str = \"{}\";",
+        event.signature()
+    ));
+    w.line("");
+    w.open("pub fn event() -> sdk::abi::Event {");
+    w.line(&format!(
+        "\"{}\".parse().expect(\"generated event signature\")",
+        event
+    ));
+    w.close("}");
+    w.line("");
+    w.open("pub fn topic() -> [u8; 32] {");
+    w.line("Self::event().topic()");
+    w.close("}");
+    w.line("");
+    w.open(
+        "pub fn decode<T: AsRef<[u8]>>(topics: &[T], data: &[u8]) -> Result<Self, sdk::Error> {",
+    );
+    if fields.is_empty() {
+        w.line("Self::event().decode_log(topics, data)?;");
+        w.line("Ok(Self {})");
+    } else {
+        w.line("let mut values = Self::event().decode_log(topics, data)?.into_iter();");
+        w.open("Ok(Self {");
+        for (field, ty) in &fields {
+            w.line(&format!(
+                "{}: {}?,",
+                field,
+                from_token(ty, "take(&mut values)?", 0)
+            ));
+        }
+        w.close("})");
+    }
+    w.close("}");
+    w.line("");
+    w.open("pub fn from_log(log: &sdk::api::transaction_info::Log) -> Result<Self, sdk::Error> {");
+    w.line("Self::decode(&log.topics, &log.data)");
+    w.close("}");
+    w.close("}");
+    Ok(())
+}
+
+fn small_int(bits: usize) -> bool {
+    matches!(bits, 8 | 16 | 32 | 64 | 128)
+}
+
+fn rust_type(ty: &ParamType) -> String {
+    match ty {
+        ParamType::Address => "sdk::client::Address".to_string(),
+        ParamType::Bool => "bool".to_string(),
+        ParamType::Uint(bits) if small_int(*bits) => format!("u{}", bits),
+        ParamType::Int(bits) if small_int(*bits) => format!("i{}", bits),
+        ParamType::Uint(_) | ParamType::TrcToken => "sdk::abi::BigUint".to_string(),
+        ParamType::Int(_) => "sdk::abi::BigInt".to_string(),
+        ParamType::FixedBytes(n) => format!("[u8; {}]", n),
+        ParamType::Bytes => "Vec<u8>".to_string(),
+        ParamType::String => "String".to_string(),
+        ParamType::Array(ty) => format!("Vec<{}>", rust_type(ty)),
+        ParamType::FixedArray(ty, n) => format!("[{}; {}]", rust_type(ty), n),
+        ParamType::Tuple(types) => tuple_type(types),
+    }
+}
+
+fn tuple_type(types: &[ParamType]) -> String {
+    tuple(types.iter().map(rust_type))
+}
+
+// `()`, `(a,)` or `(a, b)`
+fn tuple(items: impl Iterator<Item = String>) -> String {
+    let items = items.collect::<Vec<_>>();
+    match items.len() {
+        1 => format!("({},)", items[0]),
+        _ => format!("({})", items.join(", ")),
+    }
+}
+
+// an expression converting the rust value `expr` of `ty` into a `Token`
+fn to_token(ty: &ParamType, expr: &str, depth: usize) -> String {
+    let v = format!("__v{}", depth);
+    match ty {
+        ParamType::Address => format!("sdk::abi::Token::Address({})", expr),
+        ParamType::Bool => format!("sdk::abi::Token::Bool({})", expr),
+        ParamType::Uint(bits) if small_int(*bits) => {
+            format!("sdk::abi::Token::Uint(sdk::abi::BigUint::from({}))", expr)
+        }
+        ParamType::Int(bits) if small_int(*bits) => {
+            format!("sdk::abi::Token::Int(sdk::abi::BigInt::from({}))", expr)
+        }
+        ParamType::Uint(_) => format!("sdk::abi::Token::Uint({})", expr),
+        ParamType::TrcToken => format!("sdk::abi::Token::TrcToken({})", expr),
+        ParamType::Int(_) => format!("sdk::abi::Token::Int({})", expr),
+        ParamType::FixedBytes(_) => format!("sdk::abi::Token::FixedBytes({}.to_vec())", expr),
+        ParamType::Bytes => format!("sdk::abi::Token::Bytes({})", expr),
+        ParamType::String => format!("sdk::abi::Token::String({})", expr),
+        ParamType::Array(ty) => format!(
+            "sdk::abi::Token::Array({}.into_iter().map(|{}| {}).collect())",
+            expr,
+            v,
+            to_token(ty, &v, depth + 1)
+        ),
+        ParamType::FixedArray(ty, _) => format!(
+            "sdk::abi::Token::FixedArray({}.into_iter().map(|{}| {}).collect())",
+            expr,
+            v,
+            to_token(ty, &v, depth + 1)
+        ),
+        ParamType::Tuple(types) => {
+            let names = (0..types.len())
+                .map(|i| format!("__t{}_{}", depth, i))
+                .collect::<Vec<_>>();
+            let tokens = types
+                .iter()
+                .zip(&names)
+                .map(|(ty, name)| to_token(ty, name, depth + 1))
+                .collect::<Vec<_>>();
+            format!(
+                "{{ let {} = {}; sdk::abi::Token::Tuple(vec![{}]) }}",
+                tuple(names.into_iter()),
+                expr,
+                tokens.join(", ")
+            )
+        }
+    }
+}
+
+// an expression converting the `Token` expression `expr` of `ty` into a
+// `Result<rust value, sdk::Error>`
+fn from_token(ty: &ParamType, expr: &str, depth: usize) -> String {
+    let arm = |pattern: &str, value: String| {
+        format!(
+            "match {} {{ sdk::abi::Token::{}(v) => {}, t => Err(unexpected(t)) }}",
+            expr, pattern, value
+        )
+    };
+    let try_int = |int: String| {
+        format!(
+            "{}::try_from(v).map_err(|e| sdk::Error::Abi(e.to_string()))",
+            int
+        )
+    };
+    let v = format!("__v{}", depth);
+    let elements = |ty: &ParamType| {
+        format!(
+            "v.into_iter().map(|{v}| -> Result<_, sdk::Error> {{ {} }}).collect::<Result<Vec<_>, _>>()",
+            from_token(ty, &v, depth + 1),
+        )
+    };
+    match ty {
+        ParamType::Address => arm("Address", "Ok(v)".to_string()),
+        ParamType::Bool => arm("Bool", "Ok(v)".to_string()),
+        ParamType::Uint(bits) if small_int(*bits) => arm("Uint", try_int(format!("u{}", bits))),
+        ParamType::Int(bits) if small_int(*bits) => arm("Int", try_int(format!("i{}", bits))),
+        ParamType::Uint(_) => arm("Uint", "Ok(v)".to_string()),
+        ParamType::TrcToken => arm("TrcToken", "Ok(v)".to_string()),
+        ParamType::Int(_) => arm("Int", "Ok(v)".to_string()),
+        ParamType::FixedBytes(n) => arm(
+            "FixedBytes",
+            format!(
+                "<[u8; {}]>::try_from(v).map_err(|_| sdk::Error::Abi(\"invalid bytes{} length\".to_string()))",
+                n, n
+            ),
+        ),
+        ParamType::Bytes => arm("Bytes", "Ok(v)".to_string()),
+        ParamType::String => arm("String", "Ok(v)".to_string()),
+        ParamType::Array(ty) => arm("Array", elements(ty)),
+        ParamType::FixedArray(ty, n) => arm(
+            "FixedArray",
+            format!(
+                "{}.and_then(|v| <[_; {}]>::try_from(v).map_err(|_| sdk::Error::Abi(\"invalid array length\".to_string())))",
+                elements(ty),
+                n
+            ),
+        ),
+        ParamType::Tuple(types) if types.is_empty() => arm("Tuple", "Ok(())".to_string()),
+        ParamType::Tuple(types) => {
+            let it = format!("__it{}", depth);
+            let take = format!("take(&mut {})?", it);
+            let items = tuple(
+                types
+                    .iter()
+                    .map(|ty| format!("{}?", from_token(ty, &take, depth + 1))),
+            );
+            arm(
+                "Tuple",
+                format!("{{ let mut {} = v.into_iter(); Ok({}) }}", it, items),
+            )
+        }
+    }
+}
+
+// Indented lines of code.
+#[derive(Default)]
+struct Writer {
+    out: String,
+    indent: usize,
+}
+
+impl Writer {
+    fn line(&mut self, line: &str) {
+        if !line.is_empty() {
+            self.out.push_str(&"    ".repeat(self.indent));
+            self.out.push_str(line);
+        }
+        self.out.push('\n');
+    }
+
+    fn open(&mut self, line: &str) {
+        self.line(line);
+        self.indent += 1;
+    }
+
+    // closes a block, an empty `line` only dedents
+    fn close(&mut self, line: &str) {
+        self.indent -= 1;
+        if !line.is_empty() {
+            self.line(line);
+        }
+    }
+}
+
+// Unique identifiers: keywords and reserved names get a `_` suffix, a repeated name gets a
+// numeric one.
+struct Names {
+    reserved: Vec<&'static str>,
+    used: HashMap<String, usize>,
+}
+
+impl Names {
+    fn new(reserved: &[&'static str]) -> Self {
+        Self {
+            reserved: reserved.to_vec(),
+            used: HashMap::new(),
+        }
+    }
+
+    fn next(&mut self, name: &str) -> String {
+        let mut name = name.to_string();
+        if KEYWORDS.contains(&name.as_str()) || self.reserved.contains(&name.as_str()) {
+            name.push('_');
+        }
+        let count = self.used.entry(name.clone()).or_insert(0);
+        *count += 1;
+        match *count {
+            1 => name,
+            n => format!("{}_{}", name, n - 1),
+        }
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+    "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "try",
+    "type", "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "typeof", "unsized", "virtual", "yield",
+];
+
+fn param_name(name: &str, index: usize) -> String {
+    match snake_case(name) {
+        n if n.is_empty() => format!("arg{}", index),
+        n => n,
+    }
+}
+
+// `balanceOf` -> `balance_of`, `tokenURI` -> `token_uri`, `_to` -> `to`
+fn snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut out = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            out.push('_');
+            continue;
+        }
+        if c.is_ascii_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_lower)
+            {
+                out.push('_');
+            }
+        }
+        out.push(c.to_ascii_lowercase());
+    }
+    let out = out
+        .split('_')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    match out.starts_with(|c: char| c.is_ascii_digit()) {
+        true => format!("_{}", out),
+        false => out,
+    }
+}
+
+fn pascal_case(name: &str) -> String {
+    snake_case(name)
+        .split('_')
+        .map(|s| {
+            let mut chars = s.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn is_type_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use num_bigint::{BigInt, BigUint};
+
+    use super::{configure, snake_case};
+    use crate::{
+        abi::{JsonAbi, ParamType, Token, encode},
+        client::Address,
+    };
+
+    // the methods need a node
+    #[allow(dead_code)]
+    mod sample {
+        include!("testdata/sample.rs");
+    }
+
+    #[test]
+    fn test_generate() {
+        let abi = JsonAbi::from_json(include_str!("testdata/sample.json")).unwrap();
+        let code = configure()
+            .sdk_path("crate")
+            .generate(&abi, "Sample")
+            .unwrap();
+        if std::env::var_os("REGENERATE_BINDINGS").is_some() {
+            fs::write("src/abi/testdata/sample.rs", &code).unwrap();
+        }
+        assert_eq!(
+            code,
+            include_str!("testdata/sample.rs"),
+            "run with REGENERATE_BINDINGS=1 to update testdata/sample.rs"
+        );
+        assert!(configure().generate(&abi, "sample").is_err());
+
+        assert_eq!(snake_case("balanceOf"), "balance_of");
+        assert_eq!(snake_case("tokenURI"), "token_uri");
+        assert_eq!(snake_case("_to"), "to");
+        assert_eq!(snake_case("ERC20Token"), "erc20_token");
+    }
+
+    #[test]
+    fn test_generated_events() {
+        use sample::sample::{PausedEvent, TaggedEvent, Transfer1Event, TransferEvent};
+
+        let from: Address = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".parse().unwrap();
+        let to: Address = "TUEZSdKsoDHQMeZwihtdoBiN46zxhGWYdH".parse().unwrap();
+        let topics = vec![
+            TransferEvent::topic().to_vec(),
+            encode(&[ParamType::Address], &[from.into()]).unwrap(),
+            encode(&[ParamType::Address], &[to.into()]).unwrap(),
+        ];
+        let data = encode(&[ParamType::Uint(256)], &[1000u64.into()]).unwrap();
+        assert_eq!(
+            TransferEvent::decode(&topics, &data).unwrap(),
+            TransferEvent {
+                from,
+                to,
+                value: BigUint::from(1000u32),
+            }
+        );
+        // the overload with an indexed token id has the same topic, but one more indexed value
+        assert_eq!(Transfer1Event::topic().to_vec(), topics[0]);
+        let mut nft_topics = topics.clone();
+        nft_topics.push(data.clone());
+        assert!(Transfer1Event::decode(&topics, &data).is_err());
+        assert_eq!(
+            Transfer1Event::decode(&nft_topics, &[]).unwrap().token_id,
+            BigUint::from(1000u32)
+        );
+        assert!(PausedEvent::decode(&topics, &data).is_err());
+        assert_eq!(
+            PausedEvent::decode(&[PausedEvent::topic()], &[]).unwrap(),
+            PausedEvent {}
+        );
+
+        let types = [
+            ParamType::Int(256),
+            // wider than the `uint32[]` of the event
+            ParamType::Array(Box::new(ParamType::Uint(256))),
+        ];
+        let tagged = |ids: Vec<u64>| {
+            let ids = ids.into_iter().map(Token::from).collect();
+            encode(&types, &[(-5i64).into(), Token::Array(ids)]).unwrap()
+        };
+        let topics = [TaggedEvent::topic(), [9; 32]];
+        assert_eq!(
+            TaggedEvent::decode(&topics, &tagged(vec![1, 2])).unwrap(),
+            TaggedEvent {
+                tag: [9; 32],
+                arg1: BigInt::from(-5),
+                ids: vec![1, 2],
+            }
+        );
+        assert!(TaggedEvent::decode(&topics, &tagged(vec![1 << 32])).is_err());
+    }
+}
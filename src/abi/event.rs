@@ -0,0 +1,227 @@
+use std::{fmt, str::FromStr};
+
+use sha3::{Digest, Keccak256};
+
+use crate::{
+    Error,
+    abi::{ParamType, Token, decode, function, param_type},
+};
+
+/// An event parameter, `indexed` parameters are stored in the log topics.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EventParam {
+    pub kind: ParamType,
+    pub indexed: bool,
+}
+
+/// A contract event, parsed from a human-readable signature like
+/// `Transfer(address indexed from, address indexed to, uint256 value)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Event {
+    pub name: String,
+    pub inputs: Vec<EventParam>,
+    /// The log has no signature topic.
+    pub anonymous: bool,
+}
+
+impl Event {
+    pub fn new(name: &str, inputs: Vec<EventParam>, anonymous: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            inputs,
+            anonymous,
+        }
+    }
+
+    /// The canonical signature, e.g. `Transfer(address,address,uint256)`.
+    pub fn signature(&self) -> String {
+        let types = self
+            .inputs
+            .iter()
+            .map(|p| p.kind.clone())
+            .collect::<Vec<_>>();
+        format!("{}({})", self.name, param_type::join(&types))
+    }
+
+    /// The first log topic of a non-anonymous event, `keccak256(signature)`.
+    pub fn topic(&self) -> [u8; 32] {
+        Keccak256::digest(self.signature().as_bytes()).into()
+    }
+
+    /// Decodes a log into the values of `inputs`, in order. Indexed strings, bytes, arrays and
+    /// tuples are only stored as the keccak256 hash of their value, they are returned as
+    /// `bytes32` values.
+    pub fn decode_log<T: AsRef<[u8]>>(
+        &self,
+        topics: &[T],
+        data: &[u8],
+    ) -> Result<Vec<Token>, Error> {
+        let topics = match (self.anonymous, topics.split_first()) {
+            (true, _) => topics,
+            (false, Some((topic, rest))) if topic.as_ref() == self.topic() => rest,
+            _ => {
+                return Err(Error::Abi(format!(
+                    "{}: log topic mismatch",
+                    self.signature()
+                )));
+            }
+        };
+        let indexed = self.inputs.iter().filter(|p| p.indexed).count();
+        if topics.len() != indexed {
+            return Err(Error::Abi(format!(
+                "{}: get {} indexed topics, expect {}",
+                self.signature(),
+                topics.len(),
+                indexed
+            )));
+        }
+
+        let context = |e: Error| match e {
+            Error::Abi(msg) => Error::Abi(format!("{}: {}", self.signature(), msg)),
+            e => e,
+        };
+        let data_types = self
+            .inputs
+            .iter()
+            .filter(|p| !p.indexed)
+            .map(|p| p.kind.clone())
+            .collect::<Vec<_>>();
+        let mut data = decode(&data_types, data).map_err(context)?.into_iter();
+        let mut topics = topics.iter();
+        self.inputs
+            .iter()
+            .map(|p| match p.indexed {
+                false => Ok(data.next().expect("decoded data values")),
+                true => {
+                    let topic = topics.next().expect("topic count checked").as_ref();
+                    // reference types are hashed
+                    let kind = match p.kind {
+                        ParamType::Bytes
+                        | ParamType::String
+                        | ParamType::Array(_)
+                        | ParamType::FixedArray(..)
+                        | ParamType::Tuple(_) => ParamType::FixedBytes(32),
+                        _ => p.kind.clone(),
+                    };
+                    decode(&[kind], topic)
+                        .map(|mut t| t.remove(0))
+                        .map_err(context)
+                }
+            })
+            .collect()
+    }
+}
+
+/// `name(params)` with optional `indexed` parameters and a trailing `anonymous`, a leading
+/// `event` is ignored.
+impl FromStr for Event {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::Abi(format!("invalid event signature: {}", s));
+        let sig = s.trim();
+        let sig = sig.strip_prefix("event ").unwrap_or(sig).trim_start();
+        let (sig, anonymous) = match sig.strip_suffix("anonymous") {
+            Some(sig) => (sig.trim_end(), true),
+            None => (sig, false),
+        };
+
+        let open = sig.find('(').ok_or_else(invalid)?;
+        let name = sig[..open].trim();
+        let params = sig[open..]
+            .strip_prefix('(')
+            .and_then(|p| p.strip_suffix(')'))
+            .ok_or_else(invalid)?;
+        if !function::is_identifier(name) {
+            return Err(invalid());
+        }
+
+        let inputs = param_type::split_params(params)?
+            .into_iter()
+            .map(|p| {
+                let (kind, rest) = function::split_param(p)?;
+                Ok(EventParam {
+                    kind,
+                    indexed: rest.split_whitespace().next() == Some("indexed"),
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(Self::new(name, inputs, anonymous))
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(", self.name)?;
+        for (i, p) in self.inputs.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}", p.kind)?;
+            if p.indexed {
+                f.write_str(" indexed")?;
+            }
+        }
+        f.write_str(")")?;
+        if self.anonymous {
+            f.write_str(" anonymous")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use num_bigint::BigUint;
+
+    use super::Event;
+    use crate::{
+        abi::{ParamType, Token, encode},
+        client::Address,
+    };
+
+    #[test]
+    fn test_decode_log() {
+        let event: Event =
+            "event Transfer(address indexed from, address indexed to, uint256 value)"
+                .parse()
+                .unwrap();
+        assert_eq!(event.signature(), "Transfer(address,address,uint256)");
+        assert_eq!(
+            hex::encode(event.topic()),
+            "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+        assert_eq!(
+            event.to_string(),
+            "Transfer(address indexed,address indexed,uint256)"
+        );
+        assert_eq!(event.to_string().parse::<Event>().unwrap(), event);
+
+        let from: Address = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".parse().unwrap();
+        let to: Address = "TUEZSdKsoDHQMeZwihtdoBiN46zxhGWYdH".parse().unwrap();
+        let topics = vec![
+            event.topic().to_vec(),
+            encode(&[ParamType::Address], &[from.into()]).unwrap(),
+            encode(&[ParamType::Address], &[to.into()]).unwrap(),
+        ];
+        let data = encode(&[ParamType::Uint(256)], &[1000u64.into()]).unwrap();
+        assert_eq!(
+            event.decode_log(&topics, &data).unwrap(),
+            vec![from.into(), to.into(), Token::Uint(BigUint::from(1000u32))]
+        );
+        assert!(event.decode_log(&topics[1..], &data).is_err());
+        assert!(event.decode_log(&topics[..2], &data).is_err());
+
+        // indexed reference values are hashed, anonymous events have no signature topic
+        let event: Event = "Log(string indexed text, bytes data) anonymous"
+            .parse()
+            .unwrap();
+        assert!(event.anonymous);
+        let hash = [7u8; 32];
+        let data = encode(&[ParamType::Bytes], &[vec![1, 2].into()]).unwrap();
+        assert_eq!(
+            event.decode_log(&[hash], &data).unwrap(),
+            vec![Token::FixedBytes(hash.to_vec()), Token::Bytes(vec![1, 2])]
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use std::{fmt, str::FromStr};
+
+use sha3::{Digest, Keccak256};
+
+use crate::{
+    Error,
+    abi::{ParamType, Token, decode, encode, param_type},
+};
+
+/// The 4-byte selector of a function signature, e.g. `transfer(address,uint256)`.
+pub fn selector(signature: &str) -> [u8; 4] {
+    Keccak256::digest(signature.as_bytes())[..4]
+        .try_into()
+        .expect("keccak256 digest is 32 bytes")
+}
+
+/// A contract function, parsed from a human-readable signature like
+/// `balanceOf(address) returns (uint256)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Function {
+    pub name: String,
+    pub inputs: Vec<ParamType>,
+    pub outputs: Vec<ParamType>,
+}
+
+impl Function {
+    pub fn new(name: &str, inputs: Vec<ParamType>, outputs: Vec<ParamType>) -> Self {
+        Self {
+            name: name.to_string(),
+            inputs,
+            outputs,
+        }
+    }
+
+    /// The canonical signature, e.g. `transfer(address,uint256)`.
+    pub fn signature(&self) -> String {
+        format!("{}({})", self.name, param_type::join(&self.inputs))
+    }
+
+    pub fn selector(&self) -> [u8; 4] {
+        selector(&self.signature())
+    }
+
+    /// Call data: the selector followed by the encoded `args`.
+    pub fn encode_input(&self, args: &[Token]) -> Result<Vec<u8>, Error> {
+        let mut data = self.selector().to_vec();
+        data.extend(encode(&self.inputs, args).map_err(|e| self.context(e))?);
+        Ok(data)
+    }
+
+    /// Decodes call data, checking the selector.
+    pub fn decode_input(&self, data: &[u8]) -> Result<Vec<Token>, Error> {
+        match data.split_at_checked(4) {
+            Some((selector, args)) if selector == self.selector() => {
+                decode(&self.inputs, args).map_err(|e| self.context(e))
+            }
+            _ => Err(Error::Abi(format!(
+                "{}: call data selector mismatch",
+                self.signature()
+            ))),
+        }
+    }
+
+    /// Decodes the return data of a call(`constant_result`).
+    pub fn decode_output(&self, data: &[u8]) -> Result<Vec<Token>, Error> {
+        decode(&self.outputs, data).map_err(|e| self.context(e))
+    }
+
+    fn context(&self, e: Error) -> Error {
+        match e {
+            Error::Abi(msg) => Error::Abi(format!("{}: {}", self.signature(), msg)),
+            e => e,
+        }
+    }
+}
+
+// Solidity visibility and mutability keywords, they do not change the signature
+const MODIFIERS: [&str; 8] = [
+    "external",
+    "public",
+    "internal",
+    "private",
+    "view",
+    "pure",
+    "payable",
+    "nonpayable",
+];
+
+/// `name(inputs)` or `name(inputs) returns (outputs)`, a leading `function` and modifiers like
+/// `external view` before `returns` are ignored.
+impl FromStr for Function {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::Abi(format!("invalid function signature: {}", s));
+        let sig = s.trim();
+        let sig = sig.strip_prefix("function ").unwrap_or(sig).trim_start();
+
+        let open = sig.find('(').ok_or_else(invalid)?;
+        let name = sig[..open].trim();
+        if !is_identifier(name) {
+            return Err(invalid());
+        }
+        let (inputs, rest) = split_group(&sig[open..]).ok_or_else(invalid)?;
+        let mut rest = rest.trim();
+        loop {
+            let word = rest
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(rest.len());
+            if !MODIFIERS.contains(&&rest[..word]) {
+                break;
+            }
+            rest = rest[word..].trim_start();
+        }
+        let outputs = match rest.strip_prefix("returns") {
+            Some(outputs) => {
+                let (outputs, rest) = split_group(outputs.trim_start()).ok_or_else(invalid)?;
+                if !rest.trim().is_empty() {
+                    return Err(invalid());
+                }
+                params(outputs)?
+            }
+            None if rest.is_empty() => Vec::new(),
+            None => return Err(invalid()),
+        };
+
+        Ok(Self::new(name, params(inputs)?, outputs))
+    }
+}
+
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.signature())?;
+        if !self.outputs.is_empty() {
+            write!(f, " returns ({})", param_type::join(&self.outputs))?;
+        }
+        Ok(())
+    }
+}
+
+// `(...)rest` -> (`...`, `rest`)
+fn split_group(s: &str) -> Option<(&str, &str)> {
+    let mut depth = 0usize;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some((&s[1..i], &s[i + 1..]));
+                }
+            }
+            _ if depth == 0 => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+pub(crate) fn is_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+// A type name, optionally followed by modifiers and a parameter name, e.g.
+// `(address to, uint256 value)[] calldata orders` -> (`(address,uint256)[]`, `calldata orders`)
+pub(crate) fn split_param(p: &str) -> Result<(ParamType, &str), Error> {
+    let p = p.trim();
+    if p.starts_with('(') {
+        let (inner, rest) =
+            split_group(p).ok_or_else(|| Error::Abi(format!("invalid abi type: {}", p)))?;
+        let suffix_len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let (suffix, rest) = rest.split_at(suffix_len);
+        let kind = format!("{}{}", ParamType::Tuple(params(inner)?), suffix).parse()?;
+        Ok((kind, rest.trim()))
+    } else {
+        let (kind, rest) = p.split_once(char::is_whitespace).unwrap_or((p, ""));
+        Ok((kind.parse()?, rest.trim()))
+    }
+}
+
+fn params(s: &str) -> Result<Vec<ParamType>, Error> {
+    param_type::split_params(s)?
+        .into_iter()
+        .map(|p| split_param(p).map(|(kind, _)| kind))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Function, selector};
+    use crate::{
+        abi::{ParamType, Token},
+        client::Address,
+    };
+
+    #[test]
+    fn test_function() {
+        assert_eq!(hex::encode(selector("baz(uint32,bool)")), "cdcd77c0");
+
+        let f: Function = "function transfer(address to, uint256 value) returns (bool)"
+            .parse()
+            .unwrap();
+        assert_eq!(f.signature(), "transfer(address,uint256)");
+        assert_eq!(hex::encode(f.selector()), "a9059cbb");
+        assert_eq!(f.outputs, vec![ParamType::Bool]);
+        assert_eq!(f.to_string(), "transfer(address,uint256) returns (bool)");
+
+        let to: Address = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".parse().unwrap();
+        let args = vec![to.into(), 100u64.into()];
+        let data = f.encode_input(&args).unwrap();
+        assert_eq!(
+            hex::encode(&data),
+            format!("a9059cbb{:0>64}{:0>64}", to.to_evm_hex(), "64")
+        );
+        assert_eq!(f.decode_input(&data).unwrap(), args);
+        assert!(f.decode_input(&data[1..]).is_err());
+        assert!(f.encode_input(&[to.into()]).is_err());
+        assert_eq!(
+            f.decode_output(&[[0; 31].as_slice(), &[1]].concat())
+                .unwrap(),
+            vec![Token::Bool(true)]
+        );
+
+        let f: Function = "swap((address to, uint256)[] calldata orders, uint[2], bytes memory) \
+             returns (uint256 out, int8)"
+            .parse()
+            .unwrap();
+        let sig = "swap((address,uint256)[],uint256[2],bytes)";
+        assert_eq!(f.signature(), sig);
+        assert_eq!(f.outputs, vec![ParamType::Uint(256), ParamType::Int(8)]);
+        assert_eq!(
+            "totalSupply()".parse::<Function>().unwrap(),
+            Function::new("totalSupply", vec![], vec![])
+        );
+        assert_eq!(
+            f.decode_output(&[0; 31]).unwrap_err().to_string(),
+            format!(
+                "abi error: {}: abi decode: offset or length 0 is out of range",
+                sig
+            )
+        );
+
+        // solidity declarations with modifiers
+        let f: Function = "function balanceOf(address owner) view returns (uint256)"
+            .parse()
+            .unwrap();
+        assert_eq!(f.to_string(), "balanceOf(address) returns (uint256)");
+        let f: Function = "transfer(address,uint256) external returns (bool)"
+            .parse()
+            .unwrap();
+        assert_eq!(f.to_string(), "transfer(address,uint256) returns (bool)");
+        assert_eq!(
+            "function deposit() external payable"
+                .parse::<Function>()
+                .unwrap(),
+            Function::new("deposit", vec![], vec![])
+        );
+
+        for s in [
+            "transfer",
+            "f(uint256) viewx returns (bool)",
+            "(address)",
+            "a(b)",
+            "f(uint256) returns",
+            "f(uint256) x",
+            "f)(",
+        ] {
+            assert!(s.parse::<Function>().is_err(), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,539 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error,
+    abi::{Event, EventParam, Function, ParamType},
+    api::smart_contract::{
+        Abi,
+        abi::{
+            Entry,
+            entry::{EntryType as ProtoEntryType, Param, StateMutabilityType},
+        },
+    },
+};
+
+/// A contract ABI in the solc JSON format.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct JsonAbi {
+    pub entries: Vec<AbiEntry>,
+}
+
+impl JsonAbi {
+    /// Accepts a bare ABI array, a compiler artifact with an `abi` field, or the `abi`
+    /// object of a node response(`{"entrys": [...]}`).
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Wrapped {
+            Bare(Vec<AbiEntry>),
+            Artifact { abi: Vec<AbiEntry> },
+            Node { entrys: Vec<AbiEntry> },
+        }
+
+        let entries = match serde_json::from_str(json)
+            .map_err(|e| Error::Abi(format!("invalid abi json: {}", e)))?
+        {
+            Wrapped::Bare(entries)
+            | Wrapped::Artifact { abi: entries }
+            | Wrapped::Node { entrys: entries } => entries,
+        };
+        Ok(Self { entries })
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        serde_json::to_string(self).map_err(|e| Error::Abi(format!("encode abi json err: {}", e)))
+    }
+
+    pub fn functions(&self) -> impl Iterator<Item = &AbiEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.kind == EntryType::Function)
+    }
+
+    pub fn events(&self) -> impl Iterator<Item = &AbiEntry> {
+        self.entries.iter().filter(|e| e.kind == EntryType::Event)
+    }
+
+    /// The first function named `name`, see `functions` for overloads.
+    pub fn function(&self, name: &str) -> Option<&AbiEntry> {
+        self.functions().find(|e| e.name == name)
+    }
+
+    pub fn event(&self, name: &str) -> Option<&AbiEntry> {
+        self.events().find(|e| e.name == name)
+    }
+}
+
+/// The ABI of a `SmartContract` returned by the node. The protobuf only has type strings, tuple
+/// parameters are stored as canonical types like `(address,uint256)[]` and converted to unnamed
+/// `components`.
+impl From<&Abi> for JsonAbi {
+    fn from(abi: &Abi) -> Self {
+        Self {
+            entries: abi.entrys.iter().map(AbiEntry::from).collect(),
+        }
+    }
+}
+
+/// The `SmartContract.abi` of a `CreateSmartContract`. Tuple `components` become canonical
+/// types, their names and `internal_type` are dropped. Fails on a parameter type that has no
+/// signature, e.g. a `tuple` without `components`.
+impl TryFrom<&JsonAbi> for Abi {
+    type Error = Error;
+
+    fn try_from(abi: &JsonAbi) -> Result<Self, Self::Error> {
+        Ok(Self {
+            entrys: abi
+                .entries
+                .iter()
+                .map(Entry::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// One function, event, error or special function of a `JsonAbi`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbiEntry {
+    #[serde(rename = "type", default)]
+    pub kind: EntryType,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    #[serde(default)]
+    pub inputs: Vec<AbiParam>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outputs: Vec<AbiParam>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_mutability: Option<StateMutability>,
+    /// Pre-0.5 compilers, replaced by `state_mutability`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constant: Option<bool>,
+    /// Pre-0.5 compilers, replaced by `state_mutability`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payable: Option<bool>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub anonymous: bool,
+}
+
+impl AbiEntry {
+    /// `state_mutability`, or derived from the legacy `constant` and `payable` flags.
+    pub fn mutability(&self) -> StateMutability {
+        self.state_mutability
+            .unwrap_or(match (self.constant, self.payable) {
+                (Some(true), _) => StateMutability::View,
+                (_, Some(true)) => StateMutability::Payable,
+                _ => StateMutability::Nonpayable,
+            })
+    }
+
+    /// A `view` or `pure` function, called with `trigger_constant_contract`.
+    pub fn is_constant(&self) -> bool {
+        matches!(
+            self.mutability(),
+            StateMutability::View | StateMutability::Pure
+        )
+    }
+
+    pub fn function(&self) -> Result<Function, Error> {
+        if self.kind != EntryType::Function {
+            return Err(Error::Abi(format!(
+                "{} {} is not a function",
+                self.kind, self.name
+            )));
+        }
+        Ok(Function::new(
+            &self.name,
+            param_types(&self.inputs)?,
+            param_types(&self.outputs)?,
+        ))
+    }
+
+    pub fn event(&self) -> Result<Event, Error> {
+        if self.kind != EntryType::Event {
+            return Err(Error::Abi(format!(
+                "{} {} is not an event",
+                self.kind, self.name
+            )));
+        }
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|p| {
+                Ok(EventParam {
+                    kind: p.param_type()?,
+                    indexed: p.indexed,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(Event::new(&self.name, inputs, self.anonymous))
+    }
+}
+
+fn param_types(params: &[AbiParam]) -> Result<Vec<ParamType>, Error> {
+    params.iter().map(AbiParam::param_type).collect()
+}
+
+impl From<&Entry> for AbiEntry {
+    fn from(entry: &Entry) -> Self {
+        let kind = match ProtoEntryType::try_from(entry.r#type) {
+            Ok(ProtoEntryType::Constructor) => EntryType::Constructor,
+            Ok(ProtoEntryType::Event) => EntryType::Event,
+            Ok(ProtoEntryType::Fallback) => EntryType::Fallback,
+            Ok(ProtoEntryType::Receive) => EntryType::Receive,
+            Ok(ProtoEntryType::Error) => EntryType::Error,
+            _ => EntryType::Function,
+        };
+        let state_mutability = match StateMutabilityType::try_from(entry.state_mutability) {
+            Ok(StateMutabilityType::Pure) => Some(StateMutability::Pure),
+            Ok(StateMutabilityType::View) => Some(StateMutability::View),
+            Ok(StateMutabilityType::Nonpayable) => Some(StateMutability::Nonpayable),
+            Ok(StateMutabilityType::Payable) => Some(StateMutability::Payable),
+            _ => None,
+        };
+        let params = |params: &[Param]| params.iter().map(AbiParam::from).collect();
+        Self {
+            kind,
+            name: entry.name.clone(),
+            inputs: params(&entry.inputs),
+            outputs: params(&entry.outputs),
+            state_mutability,
+            constant: entry.constant.then_some(true),
+            payable: entry.payable.then_some(true),
+            anonymous: entry.anonymous,
+        }
+    }
+}
+
+impl TryFrom<&AbiEntry> for Entry {
+    type Error = Error;
+
+    fn try_from(entry: &AbiEntry) -> Result<Self, Self::Error> {
+        let kind = match entry.kind {
+            EntryType::Function => ProtoEntryType::Function,
+            EntryType::Constructor => ProtoEntryType::Constructor,
+            EntryType::Receive => ProtoEntryType::Receive,
+            EntryType::Fallback => ProtoEntryType::Fallback,
+            EntryType::Event => ProtoEntryType::Event,
+            EntryType::Error => ProtoEntryType::Error,
+        };
+        let state_mutability = match entry.state_mutability {
+            Some(StateMutability::Pure) => StateMutabilityType::Pure,
+            Some(StateMutability::View) => StateMutabilityType::View,
+            Some(StateMutability::Nonpayable) => StateMutabilityType::Nonpayable,
+            Some(StateMutability::Payable) => StateMutabilityType::Payable,
+            None => StateMutabilityType::UnknownMutabilityType,
+        };
+        let params =
+            |params: &[AbiParam]| params.iter().map(Param::try_from).collect::<Result<_, _>>();
+        Ok(Self {
+            anonymous: entry.anonymous,
+            constant: entry.constant.unwrap_or(false),
+            name: entry.name.clone(),
+            inputs: params(&entry.inputs)?,
+            outputs: params(&entry.outputs)?,
+            r#type: kind as i32,
+            payable: entry.payable.unwrap_or(false),
+            state_mutability: state_mutability as i32,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbiParam {
+    #[serde(default)]
+    pub name: String,
+    /// e.g. `uint256`, or `tuple[]` with `components`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<AbiParam>,
+    /// Event parameters only.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub indexed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub internal_type: Option<String>,
+}
+
+impl AbiParam {
+    pub fn param_type(&self) -> Result<ParamType, Error> {
+        match self.kind.strip_prefix("tuple") {
+            Some(_) if self.components.is_empty() => Err(Error::Abi(format!(
+                "tuple components are missing: {}",
+                self.name
+            ))),
+            Some(suffix) => {
+                let tuple = ParamType::Tuple(param_types(&self.components)?);
+                format!("{}{}", tuple, suffix).parse()
+            }
+            None => self.kind.parse(),
+        }
+    }
+}
+
+impl AbiParam {
+    // `(address,uint256)[]` -> `tuple[]` with unnamed components
+    fn from_type(name: &str, ty: &ParamType) -> Self {
+        let mut base = ty;
+        while let ParamType::Array(ty) | ParamType::FixedArray(ty, _) = base {
+            base = ty;
+        }
+        let kind = ty.to_string();
+        match base {
+            ParamType::Tuple(types) => Self {
+                name: name.to_string(),
+                kind: format!("tuple{}", &kind[base.to_string().len()..]),
+                components: types.iter().map(|ty| Self::from_type("", ty)).collect(),
+                ..Default::default()
+            },
+            _ => Self {
+                name: name.to_string(),
+                kind,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl From<&Param> for AbiParam {
+    fn from(param: &Param) -> Self {
+        let tuple = match param.r#type.starts_with('(') {
+            true => param.r#type.parse().ok(),
+            false => None,
+        };
+        Self {
+            indexed: param.indexed,
+            ..match tuple {
+                Some(ty) => Self::from_type(&param.name, &ty),
+                None => Self {
+                    name: param.name.clone(),
+                    kind: param.r#type.clone(),
+                    ..Default::default()
+                },
+            }
+        }
+    }
+}
+
+impl TryFrom<&AbiParam> for Param {
+    type Error = Error;
+
+    fn try_from(param: &AbiParam) -> Result<Self, Self::Error> {
+        let ty = param.param_type()?;
+        let kind = match param.kind.starts_with("tuple") {
+            true => ty.to_string(),
+            false => param.kind.clone(),
+        };
+        Ok(Self {
+            indexed: param.indexed,
+            name: param.name.clone(),
+            r#type: kind,
+        })
+    }
+}
+
+/// Nodes return capitalized names like `Function`, accepted as aliases.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryType {
+    #[default]
+    #[serde(alias = "Function")]
+    Function,
+    #[serde(alias = "Constructor")]
+    Constructor,
+    #[serde(alias = "Receive")]
+    Receive,
+    #[serde(alias = "Fallback")]
+    Fallback,
+    #[serde(alias = "Event")]
+    Event,
+    #[serde(alias = "Error")]
+    Error,
+}
+
+impl fmt::Display for EntryType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Function => "function",
+            Self::Constructor => "constructor",
+            Self::Receive => "receive",
+            Self::Fallback => "fallback",
+            Self::Event => "event",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// Nodes return capitalized names like `View`, accepted as aliases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StateMutability {
+    #[serde(alias = "Pure")]
+    Pure,
+    #[serde(alias = "View")]
+    View,
+    #[serde(alias = "Nonpayable")]
+    Nonpayable,
+    #[serde(alias = "Payable")]
+    Payable,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EntryType, JsonAbi, StateMutability};
+    use crate::{
+        abi::ParamType,
+        api::smart_contract::{
+            Abi,
+            abi::{
+                Entry,
+                entry::{EntryType as ProtoEntryType, Param, StateMutabilityType},
+            },
+        },
+    };
+
+    #[test]
+    fn test_json_abi() {
+        let abi = JsonAbi::from_json(
+            r#"[
+                {"type":"constructor","inputs":[{"name":"supply","type":"uint256"}],"stateMutability":"nonpayable"},
+                {"type":"function","name":"balanceOf","inputs":[{"name":"owner","type":"address"}],"outputs":[{"name":"","type":"uint256"}],"stateMutability":"view"},
+                {"type":"function","name":"fill","inputs":[{"name":"orders","type":"tuple[]","components":[{"name":"maker","type":"address"},{"name":"amounts","type":"uint256[2]"}]}],"outputs":[],"stateMutability":"payable"},
+                {"type":"event","name":"Transfer","inputs":[{"indexed":true,"name":"from","type":"address"},{"indexed":true,"name":"to","type":"address"},{"indexed":false,"name":"value","type":"uint256"}],"anonymous":false}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(abi.entries[0].kind, EntryType::Constructor);
+        let balance_of = abi.function("balanceOf").unwrap();
+        assert!(balance_of.is_constant());
+        assert_eq!(
+            balance_of.function().unwrap().to_string(),
+            "balanceOf(address) returns (uint256)"
+        );
+        let fill = abi.function("fill").unwrap();
+        assert_eq!(fill.mutability(), StateMutability::Payable);
+        assert_eq!(
+            fill.function().unwrap().signature(),
+            "fill((address,uint256[2])[])"
+        );
+        let transfer = abi.event("Transfer").unwrap().event().unwrap();
+        assert_eq!(transfer.signature(), "Transfer(address,address,uint256)");
+        assert!(transfer.inputs[0].indexed && !transfer.inputs[2].indexed);
+        assert!(abi.event("Transfer").unwrap().function().is_err());
+
+        let json = abi.to_json().unwrap();
+        assert!(json.contains(r#""type":"event""#) && !json.contains("anonymous"));
+        assert_eq!(JsonAbi::from_json(&json).unwrap(), abi);
+
+        // the abi of a node `getcontract` response
+        let abi = JsonAbi::from_json(
+            r#"{"entrys":[{"outputs":[{"type":"uint8"}],"constant":true,"name":"decimals","stateMutability":"View","type":"Function"},
+                {"inputs":[{"name":"_to","type":"address"},{"name":"_value","type":"uint256"}],"name":"transfer","type":"Function"}]}"#,
+        )
+        .unwrap();
+        let decimals = abi.function("decimals").unwrap();
+        assert!(decimals.is_constant());
+        assert_eq!(
+            decimals.function().unwrap().outputs,
+            vec![ParamType::Uint(8)]
+        );
+        assert!(!abi.function("transfer").unwrap().is_constant());
+        assert!(JsonAbi::from_json(r#"[{"type":"method"}]"#).is_err());
+
+        // a bare tuple from a TronWeb deployment has no signature
+        let abi = JsonAbi::from_json(
+            r#"[{"type":"function","name":"fill","inputs":[{"name":"order","type":"tuple"}]},
+                {"type":"function","name":"fillAll","inputs":[{"name":"orders","type":"tuple[]"}]}]"#,
+        )
+        .unwrap();
+        for name in ["fill", "fillAll"] {
+            let err = abi.function(name).unwrap().function().unwrap_err();
+            assert!(
+                err.to_string().contains("tuple components are missing"),
+                "{}",
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_proto_abi() {
+        let abi = JsonAbi::from_json(
+            r#"[
+                {"type":"constructor","inputs":[{"name":"supply","type":"uint256"}],"stateMutability":"payable"},
+                {"type":"function","name":"fill","inputs":[{"name":"orders","type":"tuple[2][]","components":[{"name":"","type":"address"},{"name":"","type":"tuple","components":[{"name":"","type":"uint"},{"name":"","type":"bytes"}]}]}],"outputs":[],"stateMutability":"nonpayable"},
+                {"type":"function","name":"name","inputs":[],"outputs":[{"name":"","type":"string"}],"constant":true,"payable":true},
+                {"type":"event","name":"Log","inputs":[{"indexed":true,"name":"who","type":"address"},{"name":"data","type":"bytes"}],"anonymous":true},
+                {"type":"fallback","stateMutability":"payable"},
+                {"type":"error","name":"Denied","inputs":[{"name":"code","type":"uint8"}]}
+            ]"#,
+        )
+        .unwrap();
+        let proto = Abi::try_from(&abi).unwrap();
+        assert_eq!(
+            proto.entrys[1].inputs[0],
+            Param {
+                indexed: false,
+                name: "orders".to_string(),
+                r#type: "(address,(uint256,bytes))[2][]".to_string(),
+            }
+        );
+        let log = &proto.entrys[3];
+        assert_eq!(log.r#type, ProtoEntryType::Event as i32);
+        assert!(log.anonymous && log.inputs[0].indexed && !log.inputs[1].indexed);
+        assert!(proto.entrys[2].constant && proto.entrys[2].payable);
+        assert_eq!(
+            proto.entrys[4].state_mutability,
+            StateMutabilityType::Payable as i32
+        );
+
+        // unnamed tuple components with canonical types convert back unchanged
+        let mut expected = abi.clone();
+        expected.entries[1].inputs[0].components[1].components[0].kind = "uint256".to_string();
+        let json = JsonAbi::from(&proto);
+        assert_eq!(json, expected);
+        assert_eq!(Abi::try_from(&json).unwrap(), proto);
+        assert_eq!(
+            json.function("fill")
+                .unwrap()
+                .function()
+                .unwrap()
+                .signature(),
+            "fill((address,(uint256,bytes))[2][])"
+        );
+
+        // a node abi without state mutability
+        let proto = Abi {
+            entrys: vec![Entry {
+                name: "decimals".to_string(),
+                constant: true,
+                r#type: ProtoEntryType::Function as i32,
+                outputs: vec![Param {
+                    r#type: "uint8".to_string(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        let json = JsonAbi::from(&proto);
+        assert!(json.entries[0].state_mutability.is_none());
+        assert!(json.function("decimals").unwrap().is_constant());
+        assert_eq!(Abi::try_from(&json).unwrap(), proto);
+
+        // a tuple without components has no type to store
+        let abi = JsonAbi::from_json(
+            r#"[{"type":"function","name":"fill","inputs":[{"name":"orders","type":"tuple[]"}]}]"#,
+        )
+        .unwrap();
+        let err = Abi::try_from(&abi).unwrap_err();
+        assert!(
+            err.to_string().contains("tuple components are missing"),
+            "{}",
+            err
+        );
+    }
+}
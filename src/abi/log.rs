@@ -0,0 +1,155 @@
+use crate::{
+    Error,
+    abi::{AbiEntry, Event, JsonAbi, Token},
+    api::transaction_info::Log,
+    client::Address,
+};
+
+/// A `TransactionInfo` log decoded by an ABI event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedLog {
+    /// The emitting contract.
+    pub address: Address,
+    pub event: Event,
+    /// Parameter names and values in declaration order, unnamed parameters are named by
+    /// position.
+    pub params: Vec<(String, Token)>,
+}
+
+impl DecodedLog {
+    pub fn param(&self, name: &str) -> Option<&Token> {
+        self.params.iter().find(|(n, _)| n == name).map(|(_, t)| t)
+    }
+}
+
+/// The contract address of a log, logs carry the 20-byte address without the `0x41` prefix.
+pub fn log_address(log: &Log) -> Result<Address, Error> {
+    Address::from_bytes(&log.address)
+}
+
+impl JsonAbi {
+    /// Decodes `log` by the event whose signature matches the first topic. Anonymous events
+    /// are only tried when no signature matches, the first one that accepts the topics and
+    /// data is used.
+    pub fn decode_log(&self, log: &Log) -> Result<DecodedLog, Error> {
+        let address = log_address(log)?;
+        let decode = |entry: &AbiEntry, event: Event| {
+            let values = event.decode_log(&log.topics, &log.data)?;
+            let params = entry
+                .inputs
+                .iter()
+                .zip(values)
+                .enumerate()
+                .map(|(i, (p, value))| match p.name.is_empty() {
+                    true => (i.to_string(), value),
+                    false => (p.name.clone(), value),
+                })
+                .collect();
+            Ok(DecodedLog {
+                address,
+                event,
+                params,
+            })
+        };
+
+        // a matching signature topic decides the event, its error is not hidden by an
+        // anonymous event
+        let mut last_err = None;
+        for entry in self.events().filter(|e| !e.anonymous) {
+            let Ok(event) = entry.event() else {
+                continue;
+            };
+            if log.topics.first().is_some_and(|t| *t == event.topic()) {
+                match decode(entry, event) {
+                    Ok(decoded) => return Ok(decoded),
+                    Err(e) => last_err = Some(e),
+                }
+            }
+        }
+        if let Some(e) = last_err {
+            return Err(e);
+        }
+
+        for entry in self.events().filter(|e| e.anonymous) {
+            let Ok(event) = entry.event() else {
+                continue;
+            };
+            match decode(entry, event) {
+                Ok(decoded) => return Ok(decoded),
+                Err(e) => last_err = Some(e),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| {
+            Error::Abi(format!(
+                "no event of the abi matches the log topic {}",
+                log.topics.first().map(hex::encode).unwrap_or_default()
+            ))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use num_bigint::BigUint;
+
+    use crate::{
+        abi::{Event, JsonAbi, ParamType, Token, encode},
+        api::transaction_info::Log,
+        client::Address,
+    };
+
+    #[test]
+    fn test_decode_log() {
+        let abi = JsonAbi::from_json(
+            r#"[
+                {"type":"event","name":"Transfer","inputs":[{"indexed":true,"name":"from","type":"address"},{"indexed":true,"name":"to","type":"address"},{"indexed":false,"name":"value","type":"uint256"}],"anonymous":false},
+                {"type":"event","name":"Note","inputs":[{"indexed":true,"name":"","type":"bytes32"},{"indexed":false,"name":"","type":"string"}],"anonymous":true}
+            ]"#,
+        )
+        .unwrap();
+        let contract: Address = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".parse().unwrap();
+        let from: Address = "TUEZSdKsoDHQMeZwihtdoBiN46zxhGWYdH".parse().unwrap();
+        let transfer: Event = "Transfer(address indexed,address indexed,uint256)"
+            .parse()
+            .unwrap();
+        let mut log = Log {
+            address: contract.evm_bytes().to_vec(),
+            topics: vec![
+                transfer.topic().to_vec(),
+                encode(&[ParamType::Address], &[from.into()]).unwrap(),
+                encode(&[ParamType::Address], &[contract.into()]).unwrap(),
+            ],
+            data: encode(&[ParamType::Uint(256)], &[5u64.into()]).unwrap(),
+        };
+        let decoded = abi.decode_log(&log).unwrap();
+        assert_eq!(decoded.address, contract);
+        assert_eq!(decoded.event, transfer);
+        assert_eq!(decoded.param("from"), Some(&Token::Address(from)));
+        assert_eq!(
+            decoded.param("value"),
+            Some(&Token::Uint(BigUint::from(5u32)))
+        );
+
+        // no signature topic, decoded by the anonymous event
+        log.topics = vec![vec![7; 32]];
+        log.data = encode(&[ParamType::String], &["memo".into()]).unwrap();
+        let decoded = abi.decode_log(&log).unwrap();
+        assert_eq!(decoded.event.name, "Note");
+        assert_eq!(
+            decoded.params,
+            vec![
+                ("0".to_string(), Token::FixedBytes(vec![7; 32])),
+                ("1".to_string(), "memo".into())
+            ]
+        );
+
+        log.topics = vec![vec![7; 32], vec![8; 32]];
+        assert!(abi.decode_log(&log).is_err());
+
+        // a Transfer topic with malformed data is not decoded as the anonymous event
+        log.topics = vec![transfer.topic().to_vec()];
+        let err = abi.decode_log(&log).unwrap_err().to_string();
+        assert!(err.contains("Transfer(address,address,uint256)"), "{}", err);
+        assert!(JsonAbi::default().decode_log(&log).is_err());
+    }
+}
@@ -0,0 +1,24 @@
+//! Solidity ABI encoding and decoding of contract call data, return data and logs.
+//!
+//! TRON uses the ethereum ABI, except that `address` values are the 20-byte EVM form of the
+//! `0x41`-prefixed address and `trcToken` is an alias of `uint256`.
+
+pub use num_bigint::{BigInt, BigUint};
+
+mod codec;
+pub use codec::{decode, encode};
+pub mod codegen;
+mod event;
+pub use event::{Event, EventParam};
+mod function;
+pub use function::{Function, selector};
+mod json;
+mod log;
+pub use json::{AbiEntry, AbiParam, EntryType, JsonAbi, StateMutability};
+pub use log::{DecodedLog, log_address};
+mod param_type;
+pub use param_type::ParamType;
+mod token;
+pub use token::Token;
+mod token_event;
+pub use token_event::{TokenEvent, TokenLog};
@@ -0,0 +1,201 @@
+use std::{fmt, str::FromStr};
+
+use crate::Error;
+
+/// A Solidity ABI type.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ParamType {
+    /// Encoded as the 20-byte EVM address, without the `0x41` prefix.
+    Address,
+    Bool,
+    /// `uint<bits>`, `bits` is a multiple of 8 up to 256.
+    Uint(usize),
+    /// `int<bits>`, `bits` is a multiple of 8 up to 256.
+    Int(usize),
+    /// `bytes<n>`, `n` is 1 to 32.
+    FixedBytes(usize),
+    Bytes,
+    String,
+    /// The TRC10 token id, encoded like `uint256`.
+    TrcToken,
+    Array(Box<ParamType>),
+    FixedArray(Box<ParamType>, usize),
+    Tuple(Vec<ParamType>),
+}
+
+impl ParamType {
+    /// The encoding has a tail, e.g. `bytes`, `T[]` or a tuple of those.
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            Self::Bytes | Self::String | Self::Array(_) => true,
+            Self::FixedArray(ty, _) => ty.is_dynamic(),
+            Self::Tuple(types) => types.iter().any(|ty| ty.is_dynamic()),
+            _ => false,
+        }
+    }
+
+    /// Size in the head of the enclosing tuple: 32 bytes for dynamic types(the offset),
+    /// the whole encoding for static types.
+    pub(crate) fn head_len(&self) -> usize {
+        match self {
+            _ if self.is_dynamic() => 32,
+            Self::FixedArray(ty, n) => ty.head_len() * n,
+            Self::Tuple(types) => types.iter().map(|ty| ty.head_len()).sum(),
+            _ => 32,
+        }
+    }
+}
+
+/// The canonical type name used in signatures, e.g. `(uint256,address)[]`.
+impl fmt::Display for ParamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Address => f.write_str("address"),
+            Self::Bool => f.write_str("bool"),
+            Self::Uint(bits) => write!(f, "uint{}", bits),
+            Self::Int(bits) => write!(f, "int{}", bits),
+            Self::FixedBytes(n) => write!(f, "bytes{}", n),
+            Self::Bytes => f.write_str("bytes"),
+            Self::String => f.write_str("string"),
+            Self::TrcToken => f.write_str("trcToken"),
+            Self::Array(ty) => write!(f, "{}[]", ty),
+            Self::FixedArray(ty, n) => write!(f, "{}[{}]", ty, n),
+            Self::Tuple(types) => write!(f, "({})", join(types)),
+        }
+    }
+}
+
+/// Parses a type name, `uint` and `int` are aliases of `uint256` and `int256`.
+impl FromStr for ParamType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || Error::Abi(format!("invalid abi type: {}", s));
+
+        if let Some(head) = s.strip_suffix(']') {
+            let open = head.rfind('[').ok_or_else(invalid)?;
+            let ty = Box::new(head[..open].parse()?);
+            let len = &head[open + 1..];
+            return match len {
+                "" => Ok(Self::Array(ty)),
+                _ => len
+                    .parse()
+                    .map(|n| Self::FixedArray(ty, n))
+                    .map_err(|_| invalid()),
+            };
+        }
+        if let Some(inner) = s.strip_prefix('(') {
+            let inner = inner.strip_suffix(')').ok_or_else(invalid)?;
+            return split_params(inner)?
+                .into_iter()
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .map(Self::Tuple);
+        }
+
+        let bits = |digits: &str, max: usize, step: usize| match digits {
+            "" => Some(max),
+            _ => digits
+                .parse()
+                .ok()
+                .filter(|n| *n > 0 && *n <= max && n % step == 0),
+        };
+        let ty = match s {
+            "address" => Self::Address,
+            "bool" => Self::Bool,
+            "bytes" => Self::Bytes,
+            "string" => Self::String,
+            "trcToken" => Self::TrcToken,
+            _ if s.starts_with("uint") => Self::Uint(bits(&s[4..], 256, 8).ok_or_else(invalid)?),
+            _ if s.starts_with("int") => Self::Int(bits(&s[3..], 256, 8).ok_or_else(invalid)?),
+            _ if s.starts_with("bytes") && s.len() > 5 => {
+                Self::FixedBytes(bits(&s[5..], 32, 1).ok_or_else(invalid)?)
+            }
+            _ => return Err(invalid()),
+        };
+        Ok(ty)
+    }
+}
+
+/// Splits a comma separated parameter list at the top level, e.g. `uint256,(bool,bytes)[]`.
+pub(crate) fn split_params(s: &str) -> Result<Vec<&str>, Error> {
+    if s.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut params = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| Error::Abi(format!("unbalanced parentheses: {}", s)))?
+            }
+            ',' if depth == 0 => {
+                params.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return Err(Error::Abi(format!("unbalanced parentheses: {}", s)));
+    }
+    params.push(&s[start..]);
+    Ok(params)
+}
+
+pub(crate) fn join(types: &[ParamType]) -> String {
+    types
+        .iter()
+        .map(|ty| ty.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod test {
+    use super::ParamType;
+
+    #[test]
+    fn test_param_type() {
+        for s in [
+            "address",
+            "bool",
+            "uint8",
+            "int256",
+            "bytes1",
+            "bytes32",
+            "bytes",
+            "string",
+            "trcToken",
+            "uint256[]",
+            "uint32[2][]",
+            "(uint256,(address,bytes)[],string)[3]",
+            "()",
+        ] {
+            assert_eq!(s.parse::<ParamType>().unwrap().to_string(), s);
+        }
+        assert_eq!("uint".parse::<ParamType>().unwrap(), ParamType::Uint(256));
+        assert_eq!(
+            "int[ ]".parse::<ParamType>().ok(),
+            None,
+            "array length must be a number"
+        );
+
+        let ty: ParamType = "(uint256,string)[2]".parse().unwrap();
+        assert!(ty.is_dynamic());
+        let ty: ParamType = "(uint256,bool)[2]".parse().unwrap();
+        assert!(!ty.is_dynamic());
+        assert_eq!(ty.head_len(), 128);
+
+        for s in [
+            "uint7", "uint264", "int0", "bytes0", "bytes33", "address[", "(uint256", "uint256)",
+            "float", "",
+        ] {
+            assert!(s.parse::<ParamType>().is_err(), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,308 @@
+// Generated by tron_sdk_rs::abi::codegen from the Sample ABI, do not edit.
+pub mod sample {
+    #![allow(clippy::all)]
+
+    use crate as sdk;
+
+    /// Bindings of the `Sample` contract.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Sample {
+        pub address: sdk::client::Address,
+    }
+
+    impl Sample {
+        pub fn new(address: sdk::client::Address) -> Self {
+            Self { address }
+        }
+
+        /// `name() returns (string)`
+        pub async fn name(&self, client: &mut sdk::client::GrpcClient) -> Result<String, sdk::Error> {
+            let function: sdk::abi::Function = "name() returns (string)".parse()?;
+            let data = function.encode_input(&[])?;
+            let ext = client
+                .contract_call(None, &self.address.to_bs58(), data, 0, None)
+                .await?
+                .into_inner();
+            if let Some(e) = ext.result.as_ref().and_then(sdk::Error::from_return) {
+                return Err(e);
+            }
+            let result = ext.constant_result.first().ok_or_else(|| {
+                sdk::Error::UnexpectedResponse("constant result is empty".to_string())
+            })?;
+            let mut values = function.decode_output(result)?.into_iter();
+            match take(&mut values)? { sdk::abi::Token::String(v) => Ok(v), t => Err(unexpected(t)) }
+        }
+
+        /// `decimals() returns (uint8)`
+        pub async fn decimals(&self, client: &mut sdk::client::GrpcClient) -> Result<u8, sdk::Error> {
+            let function: sdk::abi::Function = "decimals() returns (uint8)".parse()?;
+            let data = function.encode_input(&[])?;
+            let ext = client
+                .contract_call(None, &self.address.to_bs58(), data, 0, None)
+                .await?
+                .into_inner();
+            if let Some(e) = ext.result.as_ref().and_then(sdk::Error::from_return) {
+                return Err(e);
+            }
+            let result = ext.constant_result.first().ok_or_else(|| {
+                sdk::Error::UnexpectedResponse("constant result is empty".to_string())
+            })?;
+            let mut values = function.decode_output(result)?.into_iter();
+            match take(&mut values)? { sdk::abi::Token::Uint(v) => u8::try_from(v).map_err(|e| sdk::Error::Abi(e.to_string())), t => Err(unexpected(t)) }
+        }
+
+        /// `balanceOf(address) returns (uint256)`
+        pub async fn balance_of(&self, client: &mut sdk::client::GrpcClient, owner: sdk::client::Address) -> Result<sdk::abi::BigUint, sdk::Error> {
+            let function: sdk::abi::Function = "balanceOf(address) returns (uint256)".parse()?;
+            let data = function.encode_input(&[sdk::abi::Token::Address(owner)])?;
+            let ext = client
+                .contract_call(None, &self.address.to_bs58(), data, 0, None)
+                .await?
+                .into_inner();
+            if let Some(e) = ext.result.as_ref().and_then(sdk::Error::from_return) {
+                return Err(e);
+            }
+            let result = ext.constant_result.first().ok_or_else(|| {
+                sdk::Error::UnexpectedResponse("constant result is empty".to_string())
+            })?;
+            let mut values = function.decode_output(result)?.into_iter();
+            match take(&mut values)? { sdk::abi::Token::Uint(v) => Ok(v), t => Err(unexpected(t)) }
+        }
+
+        /// `transfer(address,uint256) returns (bool)`
+        pub async fn transfer(&self, client: &mut sdk::client::GrpcClient, owner: &sdk::client::Address, to: sdk::client::Address, value: sdk::abi::BigUint, fee_limit: i64) -> Result<sdk::api::TransactionExtention, sdk::Error> {
+            let function: sdk::abi::Function = "transfer(address,uint256) returns (bool)".parse()?;
+            let data = function.encode_input(&[sdk::abi::Token::Address(to), sdk::abi::Token::Uint(value)])?;
+            let ext = client
+                .contract_call(Some(&owner.to_bs58()), &self.address.to_bs58(), data, 0, Some(fee_limit))
+                .await?
+                .into_inner();
+            if let Some(e) = ext.result.as_ref().and_then(sdk::Error::from_return) {
+                return Err(e);
+            }
+            Ok(ext)
+        }
+
+        /// `safeTransferFrom(address,address,uint256)`
+        pub async fn safe_transfer_from(&self, client: &mut sdk::client::GrpcClient, owner: &sdk::client::Address, from: sdk::client::Address, to: sdk::client::Address, token_id: sdk::abi::BigUint, fee_limit: i64) -> Result<sdk::api::TransactionExtention, sdk::Error> {
+            let function: sdk::abi::Function = "safeTransferFrom(address,address,uint256)".parse()?;
+            let data = function.encode_input(&[sdk::abi::Token::Address(from), sdk::abi::Token::Address(to), sdk::abi::Token::Uint(token_id)])?;
+            let ext = client
+                .contract_call(Some(&owner.to_bs58()), &self.address.to_bs58(), data, 0, Some(fee_limit))
+                .await?
+                .into_inner();
+            if let Some(e) = ext.result.as_ref().and_then(sdk::Error::from_return) {
+                return Err(e);
+            }
+            Ok(ext)
+        }
+
+        /// `safeTransferFrom(address,address,uint256,bytes)`
+        pub async fn safe_transfer_from_1(&self, client: &mut sdk::client::GrpcClient, owner: &sdk::client::Address, from: sdk::client::Address, to: sdk::client::Address, token_id: sdk::abi::BigUint, data_: Vec<u8>, fee_limit: i64) -> Result<sdk::api::TransactionExtention, sdk::Error> {
+            let function: sdk::abi::Function = "safeTransferFrom(address,address,uint256,bytes)".parse()?;
+            let data = function.encode_input(&[sdk::abi::Token::Address(from), sdk::abi::Token::Address(to), sdk::abi::Token::Uint(token_id), sdk::abi::Token::Bytes(data_)])?;
+            let ext = client
+                .contract_call(Some(&owner.to_bs58()), &self.address.to_bs58(), data, 0, Some(fee_limit))
+                .await?
+                .into_inner();
+            if let Some(e) = ext.result.as_ref().and_then(sdk::Error::from_return) {
+                return Err(e);
+            }
+            Ok(ext)
+        }
+
+        /// `getOrder(uint64) returns ((address,uint256[2],bytes32),int64)`
+        pub async fn get_order(&self, client: &mut sdk::client::GrpcClient, id: u64) -> Result<((sdk::client::Address, [sdk::abi::BigUint; 2], [u8; 32]), i64), sdk::Error> {
+            let function: sdk::abi::Function = "getOrder(uint64) returns ((address,uint256[2],bytes32),int64)".parse()?;
+            let data = function.encode_input(&[sdk::abi::Token::Uint(sdk::abi::BigUint::from(id))])?;
+            let ext = client
+                .contract_call(None, &self.address.to_bs58(), data, 0, None)
+                .await?
+                .into_inner();
+            if let Some(e) = ext.result.as_ref().and_then(sdk::Error::from_return) {
+                return Err(e);
+            }
+            let result = ext.constant_result.first().ok_or_else(|| {
+                sdk::Error::UnexpectedResponse("constant result is empty".to_string())
+            })?;
+            let mut values = function.decode_output(result)?.into_iter();
+            Ok((match take(&mut values)? { sdk::abi::Token::Tuple(v) => { let mut __it0 = v.into_iter(); Ok((match take(&mut __it0)? { sdk::abi::Token::Address(v) => Ok(v), t => Err(unexpected(t)) }?, match take(&mut __it0)? { sdk::abi::Token::FixedArray(v) => v.into_iter().map(|__v1| -> Result<_, sdk::Error> { match __v1 { sdk::abi::Token::Uint(v) => Ok(v), t => Err(unexpected(t)) } }).collect::<Result<Vec<_>, _>>().and_then(|v| <[_; 2]>::try_from(v).map_err(|_| sdk::Error::Abi("invalid array length".to_string()))), t => Err(unexpected(t)) }?, match take(&mut __it0)? { sdk::abi::Token::FixedBytes(v) => <[u8; 32]>::try_from(v).map_err(|_| sdk::Error::Abi("invalid bytes32 length".to_string())), t => Err(unexpected(t)) }?)) }, t => Err(unexpected(t)) }?, match take(&mut values)? { sdk::abi::Token::Int(v) => i64::try_from(v).map_err(|e| sdk::Error::Abi(e.to_string())), t => Err(unexpected(t)) }?))
+        }
+
+        /// `fill((address,uint256[2])[],uint8)`
+        pub async fn fill(&self, client: &mut sdk::client::GrpcClient, owner: &sdk::client::Address, orders: Vec<(sdk::client::Address, [sdk::abi::BigUint; 2])>, type_: u8, fee_limit: i64) -> Result<sdk::api::TransactionExtention, sdk::Error> {
+            let function: sdk::abi::Function = "fill((address,uint256[2])[],uint8)".parse()?;
+            let data = function.encode_input(&[sdk::abi::Token::Array(orders.into_iter().map(|__v0| { let (__t1_0, __t1_1) = __v0; sdk::abi::Token::Tuple(vec![sdk::abi::Token::Address(__t1_0), sdk::abi::Token::FixedArray(__t1_1.into_iter().map(|__v2| sdk::abi::Token::Uint(__v2)).collect())]) }).collect()), sdk::abi::Token::Uint(sdk::abi::BigUint::from(type_))])?;
+            let ext = client
+                .contract_call(Some(&owner.to_bs58()), &self.address.to_bs58(), data, 0, Some(fee_limit))
+                .await?
+                .into_inner();
+            if let Some(e) = ext.result.as_ref().and_then(sdk::Error::from_return) {
+                return Err(e);
+            }
+            Ok(ext)
+        }
+
+        /// `deposit()`
+        ///
+        /// `call_value` is the TRX in sun sent with the call.
+        pub async fn deposit(&self, client: &mut sdk::client::GrpcClient, owner: &sdk::client::Address, call_value: i64, fee_limit: i64) -> Result<sdk::api::TransactionExtention, sdk::Error> {
+            let function: sdk::abi::Function = "deposit()".parse()?;
+            let data = function.encode_input(&[])?;
+            let ext = client
+                .contract_call(Some(&owner.to_bs58()), &self.address.to_bs58(), data, call_value, Some(fee_limit))
+                .await?
+                .into_inner();
+            if let Some(e) = ext.result.as_ref().and_then(sdk::Error::from_return) {
+                return Err(e);
+            }
+            Ok(ext)
+        }
+
+        /// `ping()`
+        pub async fn ping(&self, client: &mut sdk::client::GrpcClient) -> Result<(), sdk::Error> {
+            let function: sdk::abi::Function = "ping()".parse()?;
+            let data = function.encode_input(&[])?;
+            let ext = client
+                .contract_call(None, &self.address.to_bs58(), data, 0, None)
+                .await?
+                .into_inner();
+            if let Some(e) = ext.result.as_ref().and_then(sdk::Error::from_return) {
+                return Err(e);
+            }
+            Ok(())
+        }
+    }
+
+    /// `Transfer(address indexed,address indexed,uint256)`
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct TransferEvent {
+        pub from: sdk::client::Address,
+        pub to: sdk::client::Address,
+        pub value: sdk::abi::BigUint,
+    }
+
+    impl TransferEvent {
+        pub const SIGNATURE: &'static str = "Transfer(address,address,uint256)";
+
+        pub fn event() -> sdk::abi::Event {
+            "Transfer(address indexed,address indexed,uint256)".parse().expect("generated event signature")
+        }
+
+        pub fn topic() -> [u8; 32] {
+            Self::event().topic()
+        }
+
+        pub fn decode<T: AsRef<[u8]>>(topics: &[T], data: &[u8]) -> Result<Self, sdk::Error> {
+            let mut values = Self::event().decode_log(topics, data)?.into_iter();
+            Ok(Self {
+                from: match take(&mut values)? { sdk::abi::Token::Address(v) => Ok(v), t => Err(unexpected(t)) }?,
+                to: match take(&mut values)? { sdk::abi::Token::Address(v) => Ok(v), t => Err(unexpected(t)) }?,
+                value: match take(&mut values)? { sdk::abi::Token::Uint(v) => Ok(v), t => Err(unexpected(t)) }?,
+            })
+        }
+
+        pub fn from_log(log: &sdk::api::transaction_info::Log) -> Result<Self, sdk::Error> {
+            Self::decode(&log.topics, &log.data)
+        }
+    }
+
+    /// `Transfer(address indexed,address indexed,uint256 indexed)`
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Transfer1Event {
+        pub from: sdk::client::Address,
+        pub to: sdk::client::Address,
+        pub token_id: sdk::abi::BigUint,
+    }
+
+    impl Transfer1Event {
+        pub const SIGNATURE: &'static str = "Transfer(address,address,uint256)";
+
+        pub fn event() -> sdk::abi::Event {
+            "Transfer(address indexed,address indexed,uint256 indexed)".parse().expect("generated event signature")
+        }
+
+        pub fn topic() -> [u8; 32] {
+            Self::event().topic()
+        }
+
+        pub fn decode<T: AsRef<[u8]>>(topics: &[T], data: &[u8]) -> Result<Self, sdk::Error> {
+            let mut values = Self::event().decode_log(topics, data)?.into_iter();
+            Ok(Self {
+                from: match take(&mut values)? { sdk::abi::Token::Address(v) => Ok(v), t => Err(unexpected(t)) }?,
+                to: match take(&mut values)? { sdk::abi::Token::Address(v) => Ok(v), t => Err(unexpected(t)) }?,
+                token_id: match take(&mut values)? { sdk::abi::Token::Uint(v) => Ok(v), t => Err(unexpected(t)) }?,
+            })
+        }
+
+        pub fn from_log(log: &sdk::api::transaction_info::Log) -> Result<Self, sdk::Error> {
+            Self::decode(&log.topics, &log.data)
+        }
+    }
+
+    /// `Tagged(string indexed,int256,uint32[])`
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct TaggedEvent {
+        pub tag: [u8; 32],
+        pub arg1: sdk::abi::BigInt,
+        pub ids: Vec<u32>,
+    }
+
+    impl TaggedEvent {
+        pub const SIGNATURE: &'static str = "Tagged(string,int256,uint32[])";
+
+        pub fn event() -> sdk::abi::Event {
+            "Tagged(string indexed,int256,uint32[])".parse().expect("generated event signature")
+        }
+
+        pub fn topic() -> [u8; 32] {
+            Self::event().topic()
+        }
+
+        pub fn decode<T: AsRef<[u8]>>(topics: &[T], data: &[u8]) -> Result<Self, sdk::Error> {
+            let mut values = Self::event().decode_log(topics, data)?.into_iter();
+            Ok(Self {
+                tag: match take(&mut values)? { sdk::abi::Token::FixedBytes(v) => <[u8; 32]>::try_from(v).map_err(|_| sdk::Error::Abi("invalid bytes32 length".to_string())), t => Err(unexpected(t)) }?,
+                arg1: match take(&mut values)? { sdk::abi::Token::Int(v) => Ok(v), t => Err(unexpected(t)) }?,
+                ids: match take(&mut values)? { sdk::abi::Token::Array(v) => v.into_iter().map(|__v0| -> Result<_, sdk::Error> { match __v0 { sdk::abi::Token::Uint(v) => u32::try_from(v).map_err(|e| sdk::Error::Abi(e.to_string())), t => Err(unexpected(t)) } }).collect::<Result<Vec<_>, _>>(), t => Err(unexpected(t)) }?,
+            })
+        }
+
+        pub fn from_log(log: &sdk::api::transaction_info::Log) -> Result<Self, sdk::Error> {
+            Self::decode(&log.topics, &log.data)
+        }
+    }
+
+    /// `Paused()`
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct PausedEvent {}
+
+    impl PausedEvent {
+        pub const SIGNATURE: &'static str = "Paused()";
+
+        pub fn event() -> sdk::abi::Event {
+            "Paused()".parse().expect("generated event signature")
+        }
+
+        pub fn topic() -> [u8; 32] {
+            Self::event().topic()
+        }
+
+        pub fn decode<T: AsRef<[u8]>>(topics: &[T], data: &[u8]) -> Result<Self, sdk::Error> {
+            Self::event().decode_log(topics, data)?;
+            Ok(Self {})
+        }
+
+        pub fn from_log(log: &sdk::api::transaction_info::Log) -> Result<Self, sdk::Error> {
+            Self::decode(&log.topics, &log.data)
+        }
+    }
+
+    fn take(values: &mut impl Iterator<Item = sdk::abi::Token>) -> Result<sdk::abi::Token, sdk::Error> {
+        values.next().ok_or_else(|| sdk::Error::Abi("missing abi value".to_string()))
+    }
+
+    fn unexpected(token: sdk::abi::Token) -> sdk::Error {
+        sdk::Error::Abi(format!("unexpected abi value: {}", token))
+    }
+}
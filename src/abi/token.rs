@@ -0,0 +1,335 @@
+use std::fmt;
+
+use num_bigint::{BigInt, BigUint, Sign};
+use serde_json::Value;
+
+use crate::{Error, abi::ParamType, client::Address};
+
+/// A Solidity ABI value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Token {
+    Address(Address),
+    Bool(bool),
+    Uint(BigUint),
+    Int(BigInt),
+    FixedBytes(Vec<u8>),
+    Bytes(Vec<u8>),
+    String(String),
+    TrcToken(BigUint),
+    Array(Vec<Token>),
+    FixedArray(Vec<Token>),
+    Tuple(Vec<Token>),
+}
+
+impl Token {
+    /// The value fits `ty`: same kind, integers in range, matching lengths.
+    pub fn type_check(&self, ty: &ParamType) -> bool {
+        match (self, ty) {
+            (Self::Address(_), ParamType::Address) | (Self::Bool(_), ParamType::Bool) => true,
+            (Self::Bytes(_), ParamType::Bytes) | (Self::String(_), ParamType::String) => true,
+            (Self::Uint(n), ParamType::Uint(bits)) => n.bits() <= *bits as u64,
+            (Self::TrcToken(n), ParamType::TrcToken) => n.bits() <= 256,
+            // -2^(bits-1) <= n < 2^(bits-1)
+            (Self::Int(n), ParamType::Int(bits)) => bits.checked_sub(1).is_some_and(|b| {
+                let bound = BigInt::from(1) << b;
+                *n >= -bound.clone() && *n < bound
+            }),
+            (Self::FixedBytes(b), ParamType::FixedBytes(n)) => b.len() == *n,
+            (Self::Array(tokens), ParamType::Array(ty)) => tokens.iter().all(|t| t.type_check(ty)),
+            (Self::FixedArray(tokens), ParamType::FixedArray(ty, n)) => {
+                tokens.len() == *n && tokens.iter().all(|t| t.type_check(ty))
+            }
+            (Self::Tuple(tokens), ParamType::Tuple(types)) => {
+                tokens.len() == types.len()
+                    && tokens.iter().zip(types).all(|(t, ty)| t.type_check(ty))
+            }
+            _ => false,
+        }
+    }
+
+    pub fn as_address(&self) -> Option<&Address> {
+        match self {
+            Self::Address(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// `uint` and `trcToken` values.
+    pub fn as_uint(&self) -> Option<&BigUint> {
+        match self {
+            Self::Uint(n) | Self::TrcToken(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<&BigInt> {
+        match self {
+            Self::Int(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// `bytes` and `bytes<n>` values.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Bytes(b) | Self::FixedBytes(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Elements of arrays and tuples.
+    pub fn as_slice(&self) -> Option<&[Token]> {
+        match self {
+            Self::Array(tokens) | Self::FixedArray(tokens) | Self::Tuple(tokens) => Some(tokens),
+            _ => None,
+        }
+    }
+
+    /// Converts a JSON value to a token of type `ty`. Addresses are base58 or hex strings,
+    /// integers are numbers or decimal/`0x` hex strings, bytes are hex strings, and arrays
+    /// and tuples are JSON arrays.
+    pub fn from_json(ty: &ParamType, value: &Value) -> Result<Self, Error> {
+        let invalid = || Error::Abi(format!("invalid {} value: {}", ty, value));
+        let token = match (ty, value) {
+            (ParamType::Address, Value::String(s)) => Self::Address(s.parse()?),
+            (ParamType::Bool, Value::Bool(b)) => Self::Bool(*b),
+            (ParamType::Bool, Value::String(s)) => Self::Bool(s.parse().map_err(|_| invalid())?),
+            (ParamType::Uint(_) | ParamType::TrcToken, _) => {
+                let n = json_int(value)
+                    .and_then(|n| n.to_biguint())
+                    .ok_or_else(invalid)?;
+                match ty {
+                    ParamType::TrcToken => Self::TrcToken(n),
+                    _ => Self::Uint(n),
+                }
+            }
+            (ParamType::Int(_), _) => Self::Int(json_int(value).ok_or_else(invalid)?),
+            (ParamType::FixedBytes(_) | ParamType::Bytes, Value::String(s)) => {
+                let h = s.strip_prefix("0x").unwrap_or(s);
+                let b = hex::decode(h).map_err(|_| invalid())?;
+                match ty {
+                    ParamType::Bytes => Self::Bytes(b),
+                    _ => Self::FixedBytes(b),
+                }
+            }
+            (ParamType::String, Value::String(s)) => Self::String(s.clone()),
+            (ParamType::Array(ty), Value::Array(values)) => Self::Array(
+                values
+                    .iter()
+                    .map(|v| Self::from_json(ty, v))
+                    .collect::<Result<_, _>>()?,
+            ),
+            (ParamType::FixedArray(ty, _), Value::Array(values)) => Self::FixedArray(
+                values
+                    .iter()
+                    .map(|v| Self::from_json(ty, v))
+                    .collect::<Result<_, _>>()?,
+            ),
+            (ParamType::Tuple(types), Value::Array(values)) if types.len() == values.len() => {
+                Self::Tuple(
+                    types
+                        .iter()
+                        .zip(values)
+                        .map(|(ty, v)| Self::from_json(ty, v))
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            _ => return Err(invalid()),
+        };
+        match token.type_check(ty) {
+            true => Ok(token),
+            false => Err(invalid()),
+        }
+    }
+
+    /// The JSON form accepted by `from_json`: addresses in base58, integers as decimal strings
+    /// and bytes as `0x` hex strings.
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::Address(a) => Value::String(a.to_bs58()),
+            Self::Bool(b) => Value::Bool(*b),
+            Self::Uint(n) | Self::TrcToken(n) => Value::String(n.to_string()),
+            Self::Int(n) => Value::String(n.to_string()),
+            Self::FixedBytes(b) | Self::Bytes(b) => Value::String(format!("0x{}", hex::encode(b))),
+            Self::String(s) => Value::String(s.clone()),
+            Self::Array(tokens) | Self::FixedArray(tokens) | Self::Tuple(tokens) => {
+                Value::Array(tokens.iter().map(Self::to_json).collect())
+            }
+        }
+    }
+}
+
+// a JSON integer, or a decimal or `0x` hex string with an optional `-`
+fn json_int(value: &Value) -> Option<BigInt> {
+    match value {
+        Value::Number(n) => n
+            .as_i64()
+            .map(BigInt::from)
+            .or_else(|| n.as_u64().map(BigInt::from)),
+        Value::String(s) => {
+            let (sign, s) = match s.trim().strip_prefix('-') {
+                Some(s) => (Sign::Minus, s),
+                None => (Sign::Plus, s.trim()),
+            };
+            let n = match s.strip_prefix("0x") {
+                Some(h) => BigUint::parse_bytes(h.as_bytes(), 16)?,
+                None => s.parse::<BigUint>().ok()?,
+            };
+            Some(BigInt::from_biguint(sign, n))
+        }
+        _ => None,
+    }
+}
+
+impl From<Address> for Token {
+    fn from(address: Address) -> Self {
+        Self::Address(address)
+    }
+}
+
+impl From<bool> for Token {
+    fn from(b: bool) -> Self {
+        Self::Bool(b)
+    }
+}
+
+impl From<BigUint> for Token {
+    fn from(n: BigUint) -> Self {
+        Self::Uint(n)
+    }
+}
+
+impl From<u64> for Token {
+    fn from(n: u64) -> Self {
+        Self::Uint(n.into())
+    }
+}
+
+impl From<BigInt> for Token {
+    fn from(n: BigInt) -> Self {
+        Self::Int(n)
+    }
+}
+
+impl From<i64> for Token {
+    fn from(n: i64) -> Self {
+        Self::Int(n.into())
+    }
+}
+
+impl From<&str> for Token {
+    fn from(s: &str) -> Self {
+        Self::String(s.to_string())
+    }
+}
+
+impl From<String> for Token {
+    fn from(s: String) -> Self {
+        Self::String(s)
+    }
+}
+
+impl From<Vec<u8>> for Token {
+    fn from(b: Vec<u8>) -> Self {
+        Self::Bytes(b)
+    }
+}
+
+/// Solidity-like literals, e.g. `[1, 0x0102, (TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t, "text")]`.
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |f: &mut fmt::Formatter<'_>, tokens: &[Token]| {
+            for (i, t) in tokens.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}", t)?;
+            }
+            Ok(())
+        };
+        match self {
+            Self::Address(a) => write!(f, "{}", a),
+            Self::Bool(b) => write!(f, "{}", b),
+            Self::Uint(n) | Self::TrcToken(n) => write!(f, "{}", n),
+            Self::Int(n) => write!(f, "{}", n),
+            Self::FixedBytes(b) | Self::Bytes(b) => write!(f, "0x{}", hex::encode(b)),
+            Self::String(s) => write!(f, "{:?}", s),
+            Self::Array(tokens) | Self::FixedArray(tokens) => {
+                f.write_str("[")?;
+                list(f, tokens)?;
+                f.write_str("]")
+            }
+            Self::Tuple(tokens) => {
+                f.write_str("(")?;
+                list(f, tokens)?;
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use num_bigint::{BigInt, BigUint};
+    use serde_json::json;
+
+    use super::Token;
+    use crate::{abi::ParamType, client::Address};
+
+    #[test]
+    fn test_json() {
+        let ty: ParamType = "(address,uint256,int8,bytes2,bool[],string)"
+            .parse()
+            .unwrap();
+        let to: Address = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".parse().unwrap();
+        let token = Token::from_json(
+            &ty,
+            &json!([to.to_hex(), "0x10", -3, "0x0102", [true, "false"], "text"]),
+        )
+        .unwrap();
+        assert_eq!(
+            token,
+            Token::Tuple(vec![
+                to.into(),
+                Token::Uint(BigUint::from(16u32)),
+                Token::Int(BigInt::from(-3)),
+                Token::FixedBytes(vec![1, 2]),
+                Token::Array(vec![true.into(), false.into()]),
+                "text".into(),
+            ])
+        );
+        assert_eq!(
+            token.to_json(),
+            json!([to.to_bs58(), "16", "-3", "0x0102", [true, false], "text"])
+        );
+        assert_eq!(Token::from_json(&ty, &token.to_json()).unwrap(), token);
+
+        for (ty, value) in [
+            ("uint8", json!(256)),
+            ("uint256", json!(-1)),
+            ("uint256", json!(1.5)),
+            ("int8", json!("-129")),
+            ("bytes2", json!("0x01")),
+            ("uint256[2]", json!([1])),
+            ("(bool,bool)", json!([true])),
+            ("address", json!("T123")),
+        ] {
+            let ty: ParamType = ty.parse().unwrap();
+            assert!(Token::from_json(&ty, &value).is_err(), "{} {}", ty, value);
+        }
+    }
+}
@@ -0,0 +1,340 @@
+use std::sync::LazyLock;
+
+use num_bigint::BigUint;
+
+use crate::{
+    Error,
+    abi::{Event, log::log_address},
+    api::{TransactionInfo, transaction_info::Log},
+    client::Address,
+};
+
+/// An event of the TRC20, TRC721 or TRC1155 standard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenEvent {
+    Trc20Transfer {
+        from: Address,
+        to: Address,
+        value: BigUint,
+    },
+    Trc20Approval {
+        owner: Address,
+        spender: Address,
+        value: BigUint,
+    },
+    Trc721Transfer {
+        from: Address,
+        to: Address,
+        token_id: BigUint,
+    },
+    Trc721Approval {
+        owner: Address,
+        approved: Address,
+        token_id: BigUint,
+    },
+    /// TRC721 and TRC1155.
+    ApprovalForAll {
+        owner: Address,
+        operator: Address,
+        approved: bool,
+    },
+    TransferSingle {
+        operator: Address,
+        from: Address,
+        to: Address,
+        id: BigUint,
+        value: BigUint,
+    },
+    TransferBatch {
+        operator: Address,
+        from: Address,
+        to: Address,
+        ids: Vec<BigUint>,
+        values: Vec<BigUint>,
+    },
+    Uri {
+        value: String,
+        id: BigUint,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Trc20Transfer,
+    Trc20Approval,
+    Trc721Transfer,
+    Trc721Approval,
+    ApprovalForAll,
+    TransferSingle,
+    TransferBatch,
+    Uri,
+}
+
+// TRC20 and TRC721 events share signatures, they differ in the indexed parameters
+static EVENTS: LazyLock<Vec<(Kind, Event, [u8; 32])>> = LazyLock::new(|| {
+    [
+        (
+            Kind::Trc20Transfer,
+            "Transfer(address indexed from, address indexed to, uint256 value)",
+        ),
+        (
+            Kind::Trc20Approval,
+            "Approval(address indexed owner, address indexed spender, uint256 value)",
+        ),
+        (
+            Kind::Trc721Transfer,
+            "Transfer(address indexed from, address indexed to, uint256 indexed tokenId)",
+        ),
+        (
+            Kind::Trc721Approval,
+            "Approval(address indexed owner, address indexed approved, uint256 indexed tokenId)",
+        ),
+        (
+            Kind::ApprovalForAll,
+            "ApprovalForAll(address indexed owner, address indexed operator, bool approved)",
+        ),
+        (
+            Kind::TransferSingle,
+            "TransferSingle(address indexed operator, address indexed from, address indexed to, \
+             uint256 id, uint256 value)",
+        ),
+        (
+            Kind::TransferBatch,
+            "TransferBatch(address indexed operator, address indexed from, address indexed to, \
+             uint256[] ids, uint256[] values)",
+        ),
+        (Kind::Uri, "URI(string value, uint256 indexed id)"),
+    ]
+    .into_iter()
+    .map(|(kind, sig)| {
+        let event: Event = sig.parse().expect("standard event signature");
+        let topic = event.topic();
+        (kind, event, topic)
+    })
+    .collect()
+});
+
+impl TokenEvent {
+    /// Decodes a standard event, `None` if the topics match no standard event.
+    pub fn decode<T: AsRef<[u8]>>(topics: &[T], data: &[u8]) -> Result<Option<Self>, Error> {
+        let Some(topic) = topics.first() else {
+            return Ok(None);
+        };
+        let Some((kind, event, _)) = EVENTS.iter().find(|(_, e, t)| {
+            t == topic.as_ref() && e.inputs.iter().filter(|p| p.indexed).count() + 1 == topics.len()
+        }) else {
+            return Ok(None);
+        };
+
+        let values = event.decode_log(topics, data)?;
+        let address = |i: usize| *values[i].as_address().expect("decoded address");
+        let uint = |i: usize| values[i].as_uint().expect("decoded uint").clone();
+        let uints = |i: usize| {
+            values[i]
+                .as_slice()
+                .expect("decoded array")
+                .iter()
+                .map(|t| t.as_uint().expect("decoded uint").clone())
+                .collect()
+        };
+        Ok(Some(match kind {
+            Kind::Trc20Transfer => Self::Trc20Transfer {
+                from: address(0),
+                to: address(1),
+                value: uint(2),
+            },
+            Kind::Trc20Approval => Self::Trc20Approval {
+                owner: address(0),
+                spender: address(1),
+                value: uint(2),
+            },
+            Kind::Trc721Transfer => Self::Trc721Transfer {
+                from: address(0),
+                to: address(1),
+                token_id: uint(2),
+            },
+            Kind::Trc721Approval => Self::Trc721Approval {
+                owner: address(0),
+                approved: address(1),
+                token_id: uint(2),
+            },
+            Kind::ApprovalForAll => Self::ApprovalForAll {
+                owner: address(0),
+                operator: address(1),
+                approved: values[2].as_bool().expect("decoded bool"),
+            },
+            Kind::TransferSingle => Self::TransferSingle {
+                operator: address(0),
+                from: address(1),
+                to: address(2),
+                id: uint(3),
+                value: uint(4),
+            },
+            Kind::TransferBatch => Self::TransferBatch {
+                operator: address(0),
+                from: address(1),
+                to: address(2),
+                ids: uints(3),
+                values: uints(4),
+            },
+            Kind::Uri => Self::Uri {
+                value: values[0].as_str().expect("decoded string").to_string(),
+                id: uint(1),
+            },
+        }))
+    }
+}
+
+/// A standard token event and the contract that emitted it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenLog {
+    pub contract: Address,
+    pub event: TokenEvent,
+}
+
+impl TokenLog {
+    /// `None` if the log is not a standard token event.
+    pub fn decode(log: &Log) -> Result<Option<Self>, Error> {
+        let Some(event) = TokenEvent::decode(&log.topics, &log.data)? else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            contract: log_address(log)?,
+            event,
+        }))
+    }
+
+    /// The standard token events of a transaction, in log order. Logs that do not decode,
+    /// e.g. a non-standard contract emitting a `Transfer` topic with malformed data, are
+    /// skipped.
+    pub fn from_transaction_info(info: &TransactionInfo) -> Vec<Self> {
+        info.log
+            .iter()
+            .filter_map(|log| Self::decode(log).ok().flatten())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use num_bigint::BigUint;
+
+    use super::{EVENTS, TokenEvent, TokenLog};
+    use crate::{
+        abi::{ParamType, Token, encode},
+        api::{TransactionInfo, transaction_info::Log},
+        client::Address,
+    };
+
+    fn topic(name: &str, indexed: usize) -> Vec<u8> {
+        let (_, _, topic) = EVENTS
+            .iter()
+            .find(|(_, e, _)| {
+                e.name == name && e.inputs.iter().filter(|p| p.indexed).count() == indexed
+            })
+            .unwrap();
+        topic.to_vec()
+    }
+
+    fn word(ty: ParamType, token: Token) -> Vec<u8> {
+        encode(&[ty], &[token]).unwrap()
+    }
+
+    #[test]
+    fn test_token_event() {
+        let contract: Address = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".parse().unwrap();
+        let from: Address = "TUEZSdKsoDHQMeZwihtdoBiN46zxhGWYdH".parse().unwrap();
+        let to: Address = "TD614vXXu2qH3VrMnkeKJSW4q7FUMVstar".parse().unwrap();
+        assert_eq!(
+            hex::encode(topic("Transfer", 2)),
+            "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+
+        // TRC20 and TRC721 transfers differ in the topic count
+        let trc20 = Log {
+            address: contract.evm_bytes().to_vec(),
+            topics: vec![
+                topic("Transfer", 2),
+                word(ParamType::Address, from.into()),
+                word(ParamType::Address, to.into()),
+            ],
+            data: word(ParamType::Uint(256), 1000u64.into()),
+        };
+        assert_eq!(
+            TokenLog::decode(&trc20).unwrap(),
+            Some(TokenLog {
+                contract,
+                event: TokenEvent::Trc20Transfer {
+                    from,
+                    to,
+                    value: BigUint::from(1000u32),
+                },
+            })
+        );
+        let mut trc721 = trc20.clone();
+        trc721.topics.push(word(ParamType::Uint(256), 7u64.into()));
+        trc721.data.clear();
+        assert_eq!(
+            TokenEvent::decode(&trc721.topics, &trc721.data).unwrap(),
+            Some(TokenEvent::Trc721Transfer {
+                from,
+                to,
+                token_id: BigUint::from(7u32),
+            })
+        );
+
+        let ids = |ids: &[u64]| Token::Array(ids.iter().map(|&n| n.into()).collect());
+        let batch = Log {
+            address: contract.evm_bytes().to_vec(),
+            topics: vec![
+                topic("TransferBatch", 3),
+                word(ParamType::Address, contract.into()),
+                word(ParamType::Address, from.into()),
+                word(ParamType::Address, to.into()),
+            ],
+            data: encode(
+                &[
+                    ParamType::Array(Box::new(ParamType::Uint(256))),
+                    ParamType::Array(Box::new(ParamType::Uint(256))),
+                ],
+                &[ids(&[1, 2]), ids(&[10, 20])],
+            )
+            .unwrap(),
+        };
+        assert_eq!(
+            TokenEvent::decode(&batch.topics, &batch.data).unwrap(),
+            Some(TokenEvent::TransferBatch {
+                operator: contract,
+                from,
+                to,
+                ids: vec![BigUint::from(1u32), BigUint::from(2u32)],
+                values: vec![BigUint::from(10u32), BigUint::from(20u32)],
+            })
+        );
+
+        let uri = [topic("URI", 1), word(ParamType::Uint(256), 3u64.into())];
+        let data = word(ParamType::String, "ipfs://x".into());
+        assert_eq!(
+            TokenEvent::decode(&uri, &data).unwrap(),
+            Some(TokenEvent::Uri {
+                value: "ipfs://x".to_string(),
+                id: BigUint::from(3u32),
+            })
+        );
+
+        // not standard events, or malformed data
+        assert_eq!(TokenEvent::decode(&[[1u8; 32]], &[]).unwrap(), None);
+        assert_eq!(TokenEvent::decode(&trc20.topics[..2], &[]).unwrap(), None);
+        assert!(TokenEvent::decode(&trc20.topics, &[]).is_err());
+
+        let mut malformed = trc20.clone();
+        malformed.data.pop();
+        let info = TransactionInfo {
+            log: vec![trc20, malformed, batch],
+            ..Default::default()
+        };
+        let logs = TokenLog::from_transaction_info(&info);
+        assert_eq!(logs.len(), 2);
+        assert!(matches!(logs[1].event, TokenEvent::TransferBatch { .. }));
+    }
+}
@@ -1,6 +1,6 @@
-use tonic::{Request, Status};
+use tonic::Request;
 
-use crate::{client::GrpcClient, tron::protocol::Account};
+use crate::{Error, client::GrpcClient, tron::protocol::Account};
 
 #[derive(Debug, Copy, Clone)]
 pub struct AccountResourceBalance {
@@ -9,17 +9,22 @@ pub struct AccountResourceBalance {
 }
 
 impl GrpcClient {
-    pub async fn get_account_trx_balance(&mut self, address: &str) -> Result<i64, Status> {
+    /// An account that does not exist on chain is returned with an empty `address`.
+    pub async fn get_account(&mut self, address: &str) -> Result<Account, Error> {
         let mut req = Request::new(Account::default());
         req.get_mut().address = Self::parse_address(address)?.into_inner();
         let resp = self.inner.get_account(req).await?;
-        Ok(resp.into_inner().balance)
+        Ok(resp.into_inner())
+    }
+
+    pub async fn get_account_trx_balance(&mut self, address: &str) -> Result<i64, Error> {
+        Ok(self.get_account(address).await?.balance)
     }
 
     pub async fn get_account_resource_balance(
         &mut self,
         address: &str,
-    ) -> Result<AccountResourceBalance, Status> {
+    ) -> Result<AccountResourceBalance, Error> {
         let mut req = Request::new(Account::default());
         req.get_mut().address = Self::parse_address(address)?.into_inner();
 
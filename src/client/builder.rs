@@ -0,0 +1,326 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use prost::Message;
+use tonic::Request;
+
+use crate::{
+    Error,
+    api::{
+        self, BlockExtention, BlockReference, EmptyMessage, Transaction,
+        transaction::{self, contract::ContractType},
+    },
+    client::GrpcClient,
+};
+
+/// Reference block used for TaPoS(transaction as proof of stake).
+/// https://developers.tron.network/docs/tron-protocol-transaction#tapos
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BlockRef {
+    pub number: i64,
+    /// block id = <8 bytes of block number> + <24 bytes of block header hash>
+    pub hash: [u8; 32],
+    /// block timestamp in milliseconds, zero if unknown
+    pub timestamp: i64,
+}
+
+impl BlockRef {
+    pub fn from_block_extention(block: &BlockExtention) -> Result<Self, Error> {
+        let raw = block
+            .block_header
+            .as_ref()
+            .and_then(|h| h.raw_data.as_ref())
+            .ok_or_else(|| Error::UnexpectedResponse("block header is empty".to_string()))?;
+        Ok(Self {
+            number: raw.number,
+            hash: Self::parse_hash(&block.blockid)?,
+            timestamp: raw.timestamp,
+        })
+    }
+
+    pub fn from_block_reference(block: &BlockReference) -> Result<Self, Error> {
+        Ok(Self {
+            number: block.block_num,
+            hash: Self::parse_hash(&block.block_hash)?,
+            timestamp: 0,
+        })
+    }
+
+    fn parse_hash(hash: &[u8]) -> Result<[u8; 32], Error> {
+        hash.try_into().map_err(|_| {
+            Error::UnexpectedResponse(format!("invalid block id length: {}", hash.len()))
+        })
+    }
+
+    /// The last 2 bytes of the block number.
+    pub fn ref_block_bytes(&self) -> Vec<u8> {
+        self.number.to_be_bytes()[6..].to_vec()
+    }
+
+    /// Bytes 8..16 of the block id.
+    pub fn ref_block_hash(&self) -> Vec<u8> {
+        self.hash[8..16].to_vec()
+    }
+}
+
+/// A system contract message that can be packed into `Contract.parameter`.
+pub trait ContractMessage: Message {
+    const CONTRACT_TYPE: ContractType;
+
+    fn type_url() -> String {
+        format!(
+            "type.googleapis.com/protocol.{}",
+            Self::CONTRACT_TYPE.as_str_name()
+        )
+    }
+}
+
+macro_rules! impl_contract_message {
+    ($($name:ident),* $(,)?) => {
+        $(
+            impl ContractMessage for api::$name {
+                const CONTRACT_TYPE: ContractType = ContractType::$name;
+            }
+        )*
+    };
+}
+
+impl_contract_message!(
+    AccountCreateContract,
+    TransferContract,
+    TransferAssetContract,
+    VoteAssetContract,
+    VoteWitnessContract,
+    WitnessCreateContract,
+    AssetIssueContract,
+    WitnessUpdateContract,
+    ParticipateAssetIssueContract,
+    AccountUpdateContract,
+    FreezeBalanceContract,
+    UnfreezeBalanceContract,
+    WithdrawBalanceContract,
+    UnfreezeAssetContract,
+    UpdateAssetContract,
+    ProposalCreateContract,
+    ProposalApproveContract,
+    ProposalDeleteContract,
+    SetAccountIdContract,
+    CreateSmartContract,
+    TriggerSmartContract,
+    UpdateSettingContract,
+    ExchangeCreateContract,
+    ExchangeInjectContract,
+    ExchangeWithdrawContract,
+    ExchangeTransactionContract,
+    UpdateEnergyLimitContract,
+    AccountPermissionUpdateContract,
+    ClearAbiContract,
+    UpdateBrokerageContract,
+    ShieldedTransferContract,
+    MarketSellAssetContract,
+    MarketCancelOrderContract,
+    FreezeBalanceV2Contract,
+    UnfreezeBalanceV2Contract,
+    WithdrawExpireUnfreezeContract,
+    DelegateResourceContract,
+    UnDelegateResourceContract,
+    CancelAllUnfreezeV2Contract,
+);
+
+/// Assembles a `Transaction` locally, without asking a node to create it.
+#[derive(Debug, Clone)]
+pub struct TransactionBuilder {
+    block: BlockRef,
+    contract: Option<transaction::Contract>,
+    timestamp: Option<i64>,
+    expiration: Duration,
+    fee_limit: i64,
+    memo: Vec<u8>,
+    permission_id: i32,
+}
+
+impl TransactionBuilder {
+    pub const DEFAULT_EXPIRATION: Duration = Duration::from_secs(60);
+
+    pub fn new(block: BlockRef) -> Self {
+        Self {
+            block,
+            contract: None,
+            timestamp: None,
+            expiration: Self::DEFAULT_EXPIRATION,
+            fee_limit: 0,
+            memo: Vec::new(),
+            permission_id: 0,
+        }
+    }
+
+    pub fn block(mut self, block: BlockRef) -> Self {
+        self.block = block;
+        self
+    }
+
+    /// The same transaction intent on a newer reference block, with a fresh timestamp.
+    pub(crate) fn rebuild(&self, block: BlockRef) -> Self {
+        Self {
+            block,
+            timestamp: None,
+            ..self.clone()
+        }
+    }
+
+    pub fn contract<C: ContractMessage>(self, contract: &C) -> Self {
+        self.raw_contract(transaction::Contract {
+            r#type: C::CONTRACT_TYPE as i32,
+            parameter: Some(prost_types::Any {
+                type_url: C::type_url(),
+                value: contract.encode_to_vec(),
+            }),
+            ..Default::default()
+        })
+    }
+
+    pub fn raw_contract(mut self, contract: transaction::Contract) -> Self {
+        self.contract = Some(contract);
+        self
+    }
+
+    /// Transaction timestamp in milliseconds, defaults to the current time.
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// How long after `timestamp` the transaction expires, defaults to 60 seconds.
+    pub fn expiration(mut self, expiration: Duration) -> Self {
+        self.expiration = expiration;
+        self
+    }
+
+    pub fn fee_limit(mut self, fee_limit: i64) -> Self {
+        self.fee_limit = fee_limit;
+        self
+    }
+
+    pub fn memo(mut self, memo: impl Into<Vec<u8>>) -> Self {
+        self.memo = memo.into();
+        self
+    }
+
+    pub fn permission_id(mut self, permission_id: i32) -> Self {
+        self.permission_id = permission_id;
+        self
+    }
+
+    pub fn build(self) -> Result<Transaction, Error> {
+        let mut contract = self
+            .contract
+            .ok_or_else(|| Error::InvalidArgument("transaction contract is empty".to_string()))?;
+        contract.permission_id = self.permission_id;
+
+        let timestamp = match self.timestamp {
+            Some(t) => t,
+            None => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(|e| Error::InvalidArgument(format!("system time err: {}", e)))?
+                .as_millis() as i64,
+        };
+
+        Ok(Transaction {
+            raw_data: Some(transaction::Raw {
+                ref_block_bytes: self.block.ref_block_bytes(),
+                ref_block_hash: self.block.ref_block_hash(),
+                expiration: timestamp + self.expiration.as_millis() as i64,
+                data: self.memo,
+                contract: vec![contract],
+                timestamp,
+                fee_limit: self.fee_limit,
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+}
+
+impl GrpcClient {
+    /// Fetches the latest block as the reference block of `TransactionBuilder`.
+    pub async fn get_block_ref(&mut self) -> Result<BlockRef, Error> {
+        let block = self
+            .inner
+            .get_now_block2(Request::new(EmptyMessage::default()))
+            .await?
+            .into_inner();
+        BlockRef::from_block_extention(&block)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use prost::Message;
+
+    use crate::{
+        api::{TransferContract, transaction::contract::ContractType},
+        client::{Address, BlockRef, GrpcClient, TransactionBuilder, get_client},
+    };
+
+    fn block_ref() -> BlockRef {
+        let mut hash = [0u8; 32];
+        hash[..8].copy_from_slice(&1234567i64.to_be_bytes());
+        for (i, b) in hash[8..].iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        BlockRef {
+            number: 1234567,
+            hash,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_build_transfer() {
+        let from: Address = "TXwUd9ywscLUZQcP5tPfqU266kbh3QmYxx".parse().unwrap();
+        let to: Address = "TD614vXXu2qH3VrMnkeKJSW4q7FUMVstar".parse().unwrap();
+        let transfer = TransferContract {
+            owner_address: from.into_inner(),
+            to_address: to.into_inner(),
+            amount: 1_000_000,
+        };
+
+        let tx = TransactionBuilder::new(block_ref())
+            .contract(&transfer)
+            .timestamp(1_700_000_000_000)
+            .memo("memo")
+            .permission_id(2)
+            .build()
+            .expect("build transaction err");
+        let raw = tx.raw_data.expect("raw data");
+
+        assert_eq!(raw.ref_block_bytes, vec![0xd6, 0x87]);
+        assert_eq!(raw.ref_block_hash, (0..8).collect::<Vec<u8>>());
+        assert_eq!(raw.timestamp, 1_700_000_000_000);
+        assert_eq!(raw.expiration, 1_700_000_060_000);
+        assert_eq!(raw.data, b"memo");
+
+        let contract = &raw.contract[0];
+        assert_eq!(contract.r#type, ContractType::TransferContract as i32);
+        assert_eq!(contract.permission_id, 2);
+        let param = contract.parameter.as_ref().expect("contract parameter");
+        assert_eq!(
+            param.type_url,
+            "type.googleapis.com/protocol.TransferContract"
+        );
+        assert_eq!(
+            TransferContract::decode(param.value.as_slice()).expect("decode transfer"),
+            transfer
+        );
+        assert_eq!(GrpcClient::get_tx_hash(&raw).len(), 32);
+
+        assert!(TransactionBuilder::new(block_ref()).build().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_block_ref() {
+        let mut cli = get_client().await;
+        let block = cli.get_block_ref().await.expect("get block ref err");
+        assert!(block.number > 0);
+        assert_eq!(block.hash[..8], block.number.to_be_bytes());
+    }
+}
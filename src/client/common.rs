@@ -1,43 +1,105 @@
+use std::{fmt, str::FromStr};
+
 use prost::Message;
-use secp256k1::{Secp256k1, rand};
 use sha2::{Digest, Sha256};
 use sha3::Keccak256;
-use tonic::Status;
+use tonic::Request;
 
 use crate::{
-    api, client::GrpcClient, utils::{self, bs58}
+    Error,
+    api::{self, TransactionExtention},
+    client::GrpcClient,
+    signer::Signer,
+    utils::{self, crypto::PrivateKey},
 };
 
+/// `Debug` output redacts the private key.
 #[derive(Debug, Clone)]
 pub struct CreatedAccount {
-    pub private_key_hex: String,
+    pub private_key: PrivateKey,
     pub address_bs58: String,
 }
 
-#[derive(Debug, Clone)]
-pub struct Address(Vec<u8>);
+// tron address = `0x41` + <20 bytes of ethereum address>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Address([u8; 21]);
 
 impl Address {
-    pub fn from_bs58(address: &str) -> Result<Self, String> {
-        Ok(Self(utils::bs58::decode_address(address)?))
+    pub const PREFIX: u8 = 0x41;
+
+    pub fn new(evm_address: [u8; 20]) -> Self {
+        let mut address = [Self::PREFIX; 21];
+        address[1..].copy_from_slice(&evm_address);
+        Self(address)
+    }
+
+    pub fn from_bs58(address: &str) -> Result<Self, Error> {
+        Self::from_bytes(&utils::bs58::decode_address(address)?)
+    }
+
+    /// Accepts `41`-prefixed hex (21 bytes) or EVM hex (20 bytes), with or without `0x`.
+    /// Mixed-case EVM hex is validated as an EIP-55 checksum.
+    pub fn from_hex(address: &str) -> Result<Self, Error> {
+        let h = address
+            .strip_prefix("0x")
+            .or_else(|| address.strip_prefix("0X"))
+            .unwrap_or(address);
+        let data =
+            hex::decode(h).map_err(|e| Error::Address(format!("invalid address hex: {}", e)))?;
+        let address = Self::from_bytes(&data)?;
+        if data.len() == 20
+            && h.chars().any(|c| c.is_ascii_uppercase())
+            && h.chars().any(|c| c.is_ascii_lowercase())
+            && address.to_checksum_hex()[2..] != *h
+        {
+            return Err(Error::Address(format!(
+                "invalid address checksum: {}",
+                address
+            )));
+        }
+        Ok(address)
+    }
+
+    /// Accepts the 21-byte tron form (`0x41` prefix) or the 20-byte EVM form.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+        match data.len() {
+            20 => Ok(Self::new(data.try_into().expect("length checked"))),
+            21 if data[0] == Self::PREFIX => Ok(Self(data.try_into().expect("length checked"))),
+            21 => Err(Error::Address(format!(
+                "invalid address prefix: get:{}, expect: {}",
+                data[0],
+                Self::PREFIX
+            ))),
+            n => Err(Error::Address(format!(
+                "invalid address length: get:{}, expect: 20 or 21",
+                n
+            ))),
+        }
     }
 
     pub fn from_public_key(public_key: &secp256k1::PublicKey) -> Address {
-        Address(
-            Keccak256::digest(&public_key.serialize_uncompressed().to_vec()[1..])[12..].to_vec(),
-        )
+        let h = Keccak256::digest(&public_key.serialize_uncompressed()[1..]);
+        Self::new(h[12..].try_into().expect("keccak256 digest is 32 bytes"))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 21] {
+        &self.0
+    }
+
+    pub fn evm_bytes(&self) -> [u8; 20] {
+        self.0[1..].try_into().expect("address is 21 bytes")
     }
 
     pub fn into_inner(&self) -> Vec<u8> {
-        self.0.clone()
+        self.0.to_vec()
     }
 
     pub fn to_bs58(&self) -> String {
-        utils::bs58::encode_address(self.0.clone())
+        utils::bs58::encode_address(self.0[1..].to_vec())
     }
 
     pub fn to_hex(&self) -> String {
-        hex::encode(self.0.clone())
+        hex::encode(self.0)
     }
 
     pub fn to_hex_with_prefix(&self) -> String {
@@ -45,40 +107,198 @@ impl Address {
         h.insert_str(0, "0x");
         h
     }
+
+    pub fn to_evm_hex(&self) -> String {
+        hex::encode(&self.0[1..])
+    }
+
+    // EIP-55: https://eips.ethereum.org/EIPS/eip-55
+    pub fn to_checksum_hex(&self) -> String {
+        let h = self.to_evm_hex();
+        let hash = Keccak256::digest(h.as_bytes());
+        let mut checksummed = String::with_capacity(42);
+        checksummed.push_str("0x");
+        for (i, c) in h.chars().enumerate() {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 {
+                checksummed.push(c.to_ascii_uppercase());
+            } else {
+                checksummed.push(c);
+            }
+        }
+        checksummed
+    }
+}
+
+impl FromStr for Address {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.starts_with('T') {
+            Self::from_bs58(s)
+        } else {
+            Self::from_hex(s)
+        }
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_bs58())
+    }
+}
+
+impl From<Address> for Vec<u8> {
+    fn from(address: Address) -> Self {
+        address.0.to_vec()
+    }
+}
+
+impl TryFrom<&[u8]> for Address {
+    type Error = Error;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(data)
+    }
+}
+
+impl AsRef<[u8]> for Address {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
 }
 
 impl GrpcClient {
-    pub fn parse_address(address: &str) -> Result<Address, Status> {
-        Address::from_bs58(address)
-            .map_err(|e| Status::invalid_argument(format!("parse address err: {}", e)))
+    pub fn parse_address(address: &str) -> Result<Address, Error> {
+        address.parse()
     }
 
     // https://developers.tron.network/docs/account#externally-owned-account-creation
     pub fn create_account() -> CreatedAccount {
-        let secp = Secp256k1::new();
-        let (secret_key, public_key) = secp.generate_keypair(&mut rand::rng());
-
+        let private_key = PrivateKey::random();
         CreatedAccount {
-            private_key_hex: hex::encode(secret_key.secret_bytes()),
-            address_bs58: bs58::encode_address(
-                Keccak256::digest(&public_key.serialize_uncompressed().to_vec()[1..])[12..]
-                    .to_vec(),
-            ),
+            address_bs58: private_key.address().to_bs58(),
+            private_key,
         }
     }
 
     pub fn get_tx_hash(tx_raw: &api::transaction::Raw) -> Vec<u8> {
         Sha256::digest(tx_raw.encode_to_vec()).to_vec()
     }
+
+    /// Signs the transaction created by the node, after checking its txid.
+    pub async fn sign_extention(
+        ext: TransactionExtention,
+        signer: &dyn Signer,
+    ) -> Result<api::Transaction, Error> {
+        let tx = ext
+            .transaction
+            .ok_or_else(|| Error::UnexpectedResponse("transaction is empty".to_string()))?;
+        let raw = tx.raw_data.as_ref().ok_or_else(|| {
+            Error::UnexpectedResponse("transaction raw data is empty".to_string())
+        })?;
+        if Self::get_tx_hash(raw) != ext.txid {
+            return Err(Error::UnexpectedResponse(format!(
+                "txid({}) mismatch transaction raw data",
+                hex::encode(&ext.txid)
+            )));
+        }
+        signer.sign_transaction(tx).await
+    }
+
+    /// Broadcasts a signed transaction, a rejection is decoded into `Error::Broadcast`.
+    pub async fn broadcast_transaction(&mut self, tx: api::Transaction) -> Result<(), Error> {
+        let ret = self
+            .inner
+            .broadcast_transaction(Request::new(tx))
+            .await?
+            .into_inner();
+        match Error::from_return(&ret) {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::client::GrpcClient;
+    use std::collections::HashSet;
+
+    use crate::{
+        Error,
+        client::{Address, GrpcClient},
+    };
 
     #[test]
     fn test_create_account() {
-        println!("account: {:?}", GrpcClient::create_account());
-        println!("account2: {:?}", GrpcClient::create_account());
+        let account = GrpcClient::create_account();
+        assert_eq!(
+            account.private_key.address().to_bs58(),
+            account.address_bs58
+        );
+        assert_ne!(
+            GrpcClient::create_account().private_key,
+            account.private_key
+        );
+        assert!(!format!("{:?}", account).contains(account.private_key.expose_hex().as_str()));
+    }
+
+    #[test]
+    fn test_address_parse() {
+        let expect = "TE9t1ML5HujuVkGD8qTrWoDbTtMq8LWgzi";
+        let address: Address = expect.parse().expect("parse bs58 address err");
+        assert_eq!(address.to_bs58(), expect);
+        assert_eq!(address.to_string(), expect);
+        assert_eq!(
+            address.to_hex(),
+            "412de538f71c8ee452698b919bfa527aa079cc5521"
+        );
+
+        for s in [
+            "412de538f71c8ee452698b919bfa527aa079cc5521",
+            "0x412de538f71c8ee452698b919bfa527aa079cc5521",
+            "0x2de538f71c8ee452698b919bfa527aa079cc5521",
+            "2de538f71c8ee452698b919bfa527aa079cc5521",
+        ] {
+            assert_eq!(s.parse::<Address>().expect(s), address);
+        }
+
+        assert!(matches!(
+            "TE9t1ML5HujuVkGD8qTrWoDbTtMq8LWgzj".parse::<Address>(),
+            Err(Error::Address(_))
+        ));
+        assert!(
+            "422de538f71c8ee452698b919bfa527aa079cc5521"
+                .parse::<Address>()
+                .is_err()
+        );
+        assert!(
+            "0x2de538f71c8ee452698b919bfa527aa079cc55"
+                .parse::<Address>()
+                .is_err()
+        );
+
+        let set: HashSet<Address> = [address, address].into_iter().collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_address_checksum_hex() {
+        let checksummed = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+        let address: Address = checksummed.parse().expect("parse evm address err");
+        assert_eq!(address.to_checksum_hex(), checksummed);
+        assert_eq!(
+            checksummed
+                .to_lowercase()
+                .parse::<Address>()
+                .expect("parse lowercase evm address err"),
+            address
+        );
+        assert!(
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"
+                .parse::<Address>()
+                .is_err()
+        );
     }
 }
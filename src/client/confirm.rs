@@ -0,0 +1,352 @@
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use tonic::Request;
+
+use crate::{
+    Error,
+    api::{BytesMessage, EmptyMessage, TransactionInfo, r#return::ResponseCode},
+    client::{BlockRef, GrpcClient, TransactionBuilder},
+    signer::Signer,
+};
+
+/// How final a transaction must be before `send_and_confirm` returns.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Confirmation {
+    /// Packed into a block on the full node.
+    #[default]
+    Included,
+    /// Packed into a solidified(irreversible) block.
+    Solidified,
+    /// Packed into a PBFT-finalized block, requires `GrpcClient::with_pbft`.
+    Pbft,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfirmOptions {
+    pub confirmation: Confirmation,
+    pub poll_interval: Duration,
+    pub timeout: Duration,
+    /// How many times an expired transaction is rebuilt and re-signed.
+    pub max_rebuilds: usize,
+}
+
+impl Default for ConfirmOptions {
+    fn default() -> Self {
+        Self {
+            confirmation: Confirmation::Included,
+            poll_interval: Duration::from_secs(3),
+            timeout: Duration::from_secs(120),
+            max_rebuilds: 3,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Poll {
+    Confirmed(Box<TransactionInfo>),
+    // the head block passed the transaction expiration
+    Expired(BlockRef),
+}
+
+// The node queries of `wait_confirmation`.
+#[async_trait]
+trait ConfirmSource {
+    async fn head(&mut self) -> Result<BlockRef, Error>;
+
+    async fn transaction_info(&mut self, txid: &[u8]) -> Result<Option<TransactionInfo>, Error>;
+
+    async fn is_confirmed(
+        &mut self,
+        info: &TransactionInfo,
+        confirmation: Confirmation,
+    ) -> Result<bool, Error>;
+}
+
+#[async_trait]
+impl ConfirmSource for GrpcClient {
+    async fn head(&mut self) -> Result<BlockRef, Error> {
+        self.get_block_ref().await
+    }
+
+    async fn transaction_info(&mut self, txid: &[u8]) -> Result<Option<TransactionInfo>, Error> {
+        self.get_transaction_info(txid).await
+    }
+
+    async fn is_confirmed(
+        &mut self,
+        info: &TransactionInfo,
+        confirmation: Confirmation,
+    ) -> Result<bool, Error> {
+        GrpcClient::is_confirmed(self, info, confirmation).await
+    }
+}
+
+// Polls until the transaction reaches `opts.confirmation`, or the head block passes its
+// expiration.
+async fn wait_confirmation(
+    source: &mut (dyn ConfirmSource + Send),
+    txid: &[u8],
+    expiration: i64,
+    opts: &ConfirmOptions,
+    deadline: Instant,
+) -> Result<Poll, Error> {
+    loop {
+        // the head is fetched before the transaction info: a transaction packed in between is
+        // found by the info query instead of being reported as expired and sent again
+        let head = source.head().await?;
+        match source.transaction_info(txid).await? {
+            Some(info) => {
+                let confirmed = source.is_confirmed(&info, opts.confirmation).await?;
+                if confirmed {
+                    return Ok(Poll::Confirmed(Box::new(info)));
+                }
+            }
+            None if head.timestamp > expiration => return Ok(Poll::Expired(head)),
+            None => {}
+        }
+
+        if Instant::now() >= deadline {
+            return Err(Error::Timeout {
+                txid: hex::encode(txid),
+            });
+        }
+        tokio::time::sleep(opts.poll_interval).await;
+    }
+}
+
+impl GrpcClient {
+    /// Signs and broadcasts the transaction built by `builder`, then waits until it reaches
+    /// `opts.confirmation`. An expired transaction is rebuilt from the same builder on a newer
+    /// reference block and re-signed.
+    ///
+    /// The returned `TransactionInfo` may still carry a failed `result`(e.g. out of energy).
+    pub async fn send_and_confirm(
+        &mut self,
+        builder: TransactionBuilder,
+        signer: &dyn Signer,
+        opts: &ConfirmOptions,
+    ) -> Result<TransactionInfo, Error> {
+        if opts.confirmation == Confirmation::Pbft && self.pbft.is_none() {
+            return Err(Error::InvalidArgument(
+                "pbft endpoint is not configured".to_string(),
+            ));
+        }
+
+        let deadline = Instant::now() + opts.timeout;
+        let mut builder = builder;
+        let mut rebuilds = 0;
+        loop {
+            let tx = signer.sign_transaction(builder.clone().build()?).await?;
+            let raw = tx.raw_data.as_ref().ok_or_else(|| {
+                Error::InvalidArgument("transaction raw data is empty".to_string())
+            })?;
+            let txid = Self::get_tx_hash(raw);
+            let expiration = raw.expiration;
+
+            let poll = match self.broadcast_transaction(tx).await {
+                Ok(()) => wait_confirmation(self, &txid, expiration, opts, deadline).await?,
+                // already broadcast, e.g. by a previous attempt
+                Err(e) if e.broadcast_code() == Some(ResponseCode::DupTransactionError) => {
+                    wait_confirmation(self, &txid, expiration, opts, deadline).await?
+                }
+                Err(e)
+                    if matches!(
+                        e.broadcast_code(),
+                        Some(ResponseCode::TransactionExpirationError | ResponseCode::TaposError)
+                    ) =>
+                {
+                    Poll::Expired(self.get_block_ref().await?)
+                }
+                Err(e) => return Err(e),
+            };
+
+            match poll {
+                Poll::Confirmed(info) => return Ok(*info),
+                Poll::Expired(_) if rebuilds >= opts.max_rebuilds => {
+                    return Err(Error::Expired {
+                        txid: hex::encode(txid),
+                    });
+                }
+                Poll::Expired(head) => {
+                    rebuilds += 1;
+                    builder = builder.rebuild(head);
+                }
+            }
+        }
+    }
+
+    async fn is_confirmed(
+        &mut self,
+        info: &TransactionInfo,
+        confirmation: Confirmation,
+    ) -> Result<bool, Error> {
+        let req = || {
+            Request::new(BytesMessage {
+                value: info.id.clone(),
+            })
+        };
+        match confirmation {
+            Confirmation::Included => Ok(true),
+            Confirmation::Solidified => match self.solidity.as_mut() {
+                Some(solidity) => Ok(!solidity
+                    .get_transaction_info_by_id(req())
+                    .await?
+                    .into_inner()
+                    .id
+                    .is_empty()),
+                None => Ok(self.get_solidified_block_num().await? >= info.block_number),
+            },
+            Confirmation::Pbft => match self.pbft.as_mut() {
+                Some(pbft) => Ok(!pbft
+                    .get_transaction_info_by_id(req())
+                    .await?
+                    .into_inner()
+                    .id
+                    .is_empty()),
+                None => Err(Error::InvalidArgument(
+                    "pbft endpoint is not configured".to_string(),
+                )),
+            },
+        }
+    }
+
+    /// Returns `None` if the transaction is not packed into a block yet.
+    pub async fn get_transaction_info(
+        &mut self,
+        txid: &[u8],
+    ) -> Result<Option<TransactionInfo>, Error> {
+        let info = self
+            .inner
+            .get_transaction_info_by_id(Request::new(BytesMessage {
+                value: txid.to_vec(),
+            }))
+            .await?
+            .into_inner();
+        Ok((!info.id.is_empty()).then_some(info))
+    }
+
+    /// The latest solidified block number known by the full node.
+    pub async fn get_solidified_block_num(&mut self) -> Result<i64, Error> {
+        let node = self
+            .inner
+            .get_node_info(Request::new(EmptyMessage::default()))
+            .await?
+            .into_inner();
+        parse_block_num(&node.solidity_block)
+    }
+}
+
+// node info block format: `Num:<block number>,ID:<block id>`
+fn parse_block_num(block: &str) -> Result<i64, Error> {
+    block
+        .split(',')
+        .find_map(|kv| kv.strip_prefix("Num:"))
+        .and_then(|n| n.trim().parse().ok())
+        .ok_or_else(|| Error::UnexpectedResponse(format!("invalid node info block: {}", block)))
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use async_trait::async_trait;
+
+    use super::{
+        ConfirmOptions, ConfirmSource, Confirmation, Poll, parse_block_num, wait_confirmation,
+    };
+    use crate::{Error, api::TransactionInfo, client::BlockRef};
+
+    // A node whose head block is already past the expiration. The transaction is packed
+    // `packed_after` head queries later, `None` never.
+    struct MockNode {
+        heads: usize,
+        packed_after: Option<usize>,
+    }
+
+    #[async_trait]
+    impl ConfirmSource for MockNode {
+        async fn head(&mut self) -> Result<BlockRef, Error> {
+            self.heads += 1;
+            Ok(BlockRef {
+                number: 100,
+                hash: [0; 32],
+                timestamp: 2_000,
+            })
+        }
+
+        async fn transaction_info(
+            &mut self,
+            txid: &[u8],
+        ) -> Result<Option<TransactionInfo>, Error> {
+            Ok(self
+                .packed_after
+                .is_some_and(|n| self.heads >= n)
+                .then(|| TransactionInfo {
+                    id: txid.to_vec(),
+                    block_number: 99,
+                    ..Default::default()
+                }))
+        }
+
+        async fn is_confirmed(
+            &mut self,
+            _: &TransactionInfo,
+            _: Confirmation,
+        ) -> Result<bool, Error> {
+            Ok(true)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wait_confirmation() {
+        let opts = ConfirmOptions {
+            poll_interval: Duration::from_millis(1),
+            ..Default::default()
+        };
+        let deadline = Instant::now() + Duration::from_secs(5);
+
+        // packed right after the expired head is seen, it must not be sent again
+        let mut node = MockNode {
+            heads: 0,
+            packed_after: Some(1),
+        };
+        let poll = wait_confirmation(&mut node, b"tx", 1_000, &opts, deadline)
+            .await
+            .unwrap();
+        assert!(matches!(poll, Poll::Confirmed(info) if info.id == b"tx"));
+
+        let mut node = MockNode {
+            heads: 0,
+            packed_after: None,
+        };
+        let poll = wait_confirmation(&mut node, b"tx", 1_000, &opts, deadline)
+            .await
+            .unwrap();
+        assert!(matches!(poll, Poll::Expired(head) if head.number == 100));
+
+        // not expired yet, polled until the deadline
+        let mut node = MockNode {
+            heads: 0,
+            packed_after: None,
+        };
+        let deadline = Instant::now() + Duration::from_millis(20);
+        assert!(matches!(
+            wait_confirmation(&mut node, b"tx", 5_000, &opts, deadline).await,
+            Err(Error::Timeout { .. })
+        ));
+        assert!(node.heads > 1);
+    }
+
+    #[test]
+    fn test_parse_block_num() {
+        assert_eq!(
+            parse_block_num(
+                "Num:59334167,ID:000000000389612751c50ac0b1c4bd9f6d2e7ff6e1e5b2b1c0d3a52d80f0b2d4"
+            )
+            .expect("parse block num err"),
+            59334167
+        );
+        assert!(parse_block_num("").is_err());
+    }
+}
@@ -1,60 +1,173 @@
-use num_bigint::{BigInt, Sign};
-use tonic::{Code, Request, Response, Status};
+use std::sync::Arc;
+
+use num_bigint::BigInt;
+use serde_json::{Map, Value};
+use tonic::{Request, Response};
 
 use crate::{
+    Error,
+    abi::{AbiEntry, Function, JsonAbi, StateMutability, Token},
     client::GrpcClient,
-    tron::protocol::{TransactionExtention, TriggerSmartContract},
+    signer::Signer,
+    tron::protocol::{
+        BytesMessage, SmartContract, Transaction, TransactionExtention, TriggerSmartContract,
+    },
 };
 
+/// The result of `GrpcClient::call_by_name`.
+#[derive(Debug, Clone)]
+pub enum CallOutput {
+    /// The decoded outputs of a `view` or `pure` function, in the form of `Token::to_json`,
+    /// keyed by output name, or by position for unnamed outputs.
+    Constant(Map<String, Value>),
+    /// The unsigned transaction of any other function.
+    Transaction(Box<TransactionExtention>),
+}
+
 impl GrpcClient {
+    /// `call_value` is the TRX in sun sent to a payable function.
     pub async fn contract_call(
         &mut self,
         from: Option<&str>,
         contract: &str,
-        call_data: String,
+        call_data: Vec<u8>,
+        call_value: i64,
         writable: Option<i64>, // fee limit
-    ) -> Result<Response<TransactionExtention>, Status> {
+    ) -> Result<Response<TransactionExtention>, Error> {
         let mut req = Request::new(TriggerSmartContract::default());
         if let Some(from_address) = from {
             req.get_mut().owner_address = Self::parse_address(from_address)?.into_inner();
         }
         req.get_mut().contract_address = Self::parse_address(contract)?.into_inner();
-        req.get_mut().data = hex::decode(call_data)
-            .map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))?;
+        req.get_mut().data = call_data;
+        req.get_mut().call_value = call_value;
 
         if let Some(fee_limit) = writable {
-            self.inner.trigger_contract(req).await.map(|mut resp| {
-                let ext = resp.get_mut();
-                let mut txid = ext.txid.clone();
-                if let Some(raw) = ext.transaction.as_mut().and_then(|tx| tx.raw_data.as_mut()) {
-                    raw.fee_limit = fee_limit;
-                    txid = Self::get_tx_hash(raw);
-                }
-                ext.txid = txid;
-                resp
-            })
+            let mut resp = self.inner.trigger_contract(req).await?;
+            let ext = resp.get_mut();
+            if let Some(raw) = ext.transaction.as_mut().and_then(|tx| tx.raw_data.as_mut()) {
+                raw.fee_limit = fee_limit;
+                ext.txid = Self::get_tx_hash(raw);
+            }
+            Ok(resp)
         } else {
-            self.inner.trigger_constant_contract(req).await
+            Ok(self.inner.trigger_constant_contract(req).await?)
+        }
+    }
+
+    /// A contract that does not exist on chain is returned with an empty `contract_address`.
+    pub async fn get_contract(&mut self, contract: &str) -> Result<SmartContract, Error> {
+        let req = Request::new(BytesMessage {
+            value: Self::parse_address(contract)?.into_inner(),
+        });
+        Ok(self.inner.get_contract(req).await?.into_inner())
+    }
+
+    /// The ABI of `contract`, fetched from the node once and then cached by the client.
+    pub async fn get_contract_abi(&mut self, contract: &str) -> Result<Arc<JsonAbi>, Error> {
+        let address = Self::parse_address(contract)?;
+        if let Some(abi) = self.abi_cache.get(&address) {
+            return Ok(abi.clone());
+        }
+        let abi = match self.get_contract(contract).await?.abi {
+            Some(abi) if !abi.entrys.is_empty() => Arc::new(JsonAbi::from(&abi)),
+            _ => return Err(Error::Abi(format!("contract {} has no abi", address))),
+        };
+        self.abi_cache.insert(address, abi.clone());
+        Ok(abi)
+    }
+
+    /// Replaces the cached ABI of `contract`, e.g. with the implementation ABI of a proxy.
+    pub fn set_contract_abi(&mut self, contract: &str, abi: JsonAbi) -> Result<(), Error> {
+        self.abi_cache
+            .insert(Self::parse_address(contract)?, Arc::new(abi));
+        Ok(())
+    }
+
+    pub fn clear_abi_cache(&mut self) {
+        self.abi_cache.clear();
+    }
+
+    /// Calls `function` of `contract` with JSON arguments(see `Token::from_json`), using the
+    /// contract ABI. `function` is a name, or a signature like `transfer(address,uint256)`
+    /// to pick an overload.
+    ///
+    /// `view` and `pure` functions are called with `trigger_constant_contract`. Other functions
+    /// return the unsigned transaction of `from` with `fee_limit`, sending `call_value` sun to
+    /// a payable function.
+    pub async fn call_by_name(
+        &mut self,
+        from: Option<&str>,
+        contract: &str,
+        function: &str,
+        args: &[Value],
+        call_value: i64,
+        fee_limit: i64,
+    ) -> Result<CallOutput, Error> {
+        let abi = self.get_contract_abi(contract).await?;
+        let (entry, function, tokens) = resolve_function(&abi, function, args)?;
+        check_call_value(entry, &function, call_value)?;
+        let call_data = function.encode_input(&tokens)?;
+
+        if !entry.is_constant() {
+            let from = from.ok_or_else(|| {
+                Error::InvalidArgument(format!("{} needs a caller", function.signature()))
+            })?;
+            let ext = self
+                .contract_call(Some(from), contract, call_data, call_value, Some(fee_limit))
+                .await?
+                .into_inner();
+            if let Some(e) = ext.result.as_ref().and_then(Error::from_return) {
+                return Err(e);
+            }
+            return Ok(CallOutput::Transaction(Box::new(ext)));
+        }
+
+        let ext = self
+            .contract_call(from, contract, call_data, 0, None)
+            .await?
+            .into_inner();
+        if let Some(e) = ext.result.as_ref().and_then(Error::from_return) {
+            return Err(e);
         }
+        let outputs = match ext.constant_result.first() {
+            _ if function.outputs.is_empty() => Vec::new(),
+            Some(result) => function.decode_output(result)?,
+            None => {
+                return Err(Error::UnexpectedResponse(
+                    "constant result is empty".to_string(),
+                ));
+            }
+        };
+        let outputs = entry
+            .outputs
+            .iter()
+            .zip(outputs)
+            .enumerate()
+            .map(|(i, (p, token))| match p.name.is_empty() {
+                true => (i.to_string(), token.to_json()),
+                false => (p.name.clone(), token.to_json()),
+            })
+            .collect();
+        Ok(CallOutput::Constant(outputs))
     }
 
-    pub async fn trc20_balance(&mut self, from: &str, contract: &str) -> Result<BigInt, Status> {
+    pub async fn trc20_balance(&mut self, from: &str, contract: &str) -> Result<BigInt, Error> {
         let from_address = Self::parse_address(from)?;
-        // https://learnevm.com/chapters/abi-encoding/anatomy#the-anatomy-of-an-abi-encoded-function-call
-        // call data = <function selector> + <parameters>
-        // function balanceOf(address _owner) public view returns (uint256 balance)
-        let call_data = format!("70a08231{:0>64}", from_address.to_hex());
+        let balance_of: Function = "balanceOf(address) returns (uint256)".parse()?;
+        let call_data = balance_of.encode_input(&[from_address.into()])?;
         let resp = self
-            .contract_call(Some(from), contract, call_data, None)
+            .contract_call(Some(from), contract, call_data, 0, None)
             .await?;
         let call_res = resp.into_inner().constant_result;
         if call_res.len() != 1 {
-            return Err(Status::internal(format!(
+            return Err(Error::UnexpectedResponse(format!(
                 "constant result({:?}) length is not one",
                 call_res
             )));
         }
-        Ok(BigInt::from_bytes_be(num_bigint::Sign::Plus, &call_res[0]))
+        let balance = balance_of.decode_output(&call_res[0])?;
+        Ok(balance[0].as_uint().cloned().unwrap_or_default().into())
     }
 
     pub async fn trc20_transfer(
@@ -64,30 +177,204 @@ impl GrpcClient {
         contract: &str,
         amount: BigInt,
         fee_limit: i64,
-    ) -> Result<Response<TransactionExtention>, Status> {
+    ) -> Result<Response<TransactionExtention>, Error> {
         let to_address = Self::parse_address(to)?;
-        let (sign, amount) = amount.to_bytes_be();
-        if sign != Sign::Plus {
-            return Err(Status::invalid_argument("amount is not positive"))
-        }
+        let amount = amount
+            .to_biguint()
+            .filter(|n| n.bits() > 0)
+            .ok_or_else(|| Error::InvalidArgument("amount is not positive".to_string()))?;
 
-        // function transfer(address _to, uint256 _value) public returns (bool success)
-        let call_data = format!(
-            "a9059cbb{:0>64}{:0>64}",
-            to_address.to_hex(),
-            hex::encode(amount)
-        );
+        let transfer: Function = "transfer(address,uint256) returns (bool)".parse()?;
+        let call_data = transfer.encode_input(&[to_address.into(), Token::Uint(amount)])?;
 
-        self.contract_call(Some(from), contract, call_data, Some(fee_limit))
+        self.contract_call(Some(from), contract, call_data, 0, Some(fee_limit))
             .await
     }
+
+    /// Creates a TRC20 transfer from the signer's account, signed and ready to broadcast.
+    pub async fn signed_trc20_transfer(
+        &mut self,
+        signer: &dyn Signer,
+        to: &str,
+        contract: &str,
+        amount: BigInt,
+        fee_limit: i64,
+    ) -> Result<Transaction, Error> {
+        let from = signer.address().to_bs58();
+        let ext = self
+            .trc20_transfer(&from, to, contract, amount, fee_limit)
+            .await?;
+        Self::sign_extention(ext.into_inner(), signer).await
+    }
+}
+
+// Only payable functions accept TRX.
+fn check_call_value(entry: &AbiEntry, function: &Function, call_value: i64) -> Result<(), Error> {
+    if call_value < 0 {
+        return Err(Error::InvalidArgument(format!(
+            "call value {} is negative",
+            call_value
+        )));
+    }
+    if call_value > 0 && entry.mutability() != StateMutability::Payable {
+        return Err(Error::InvalidArgument(format!(
+            "{} is not payable",
+            function.signature()
+        )));
+    }
+    Ok(())
+}
+
+// The function named, or with the signature, `name` whose inputs accept `args`.
+fn resolve_function<'a>(
+    abi: &'a JsonAbi,
+    name: &str,
+    args: &[Value],
+) -> Result<(&'a AbiEntry, Function, Vec<Token>), Error> {
+    let signature = match name.contains('(') {
+        true => Some(name.parse::<Function>()?.signature()),
+        false => None,
+    };
+    let mut found = Vec::new();
+    let mut last_err = None;
+    for entry in abi.functions() {
+        if signature.is_none() && entry.name != name {
+            continue;
+        }
+        let function = entry.function()?;
+        if signature
+            .as_ref()
+            .is_some_and(|s| *s != function.signature())
+        {
+            continue;
+        }
+        if function.inputs.len() != args.len() {
+            last_err = Some(Error::Abi(format!(
+                "{}: get {} arguments, expect {}",
+                function.signature(),
+                args.len(),
+                function.inputs.len()
+            )));
+            continue;
+        }
+        let tokens = function
+            .inputs
+            .iter()
+            .zip(args)
+            .map(|(ty, v)| Token::from_json(ty, v))
+            .collect::<Result<Vec<_>, _>>();
+        match tokens {
+            Ok(tokens) => found.push((entry, function, tokens)),
+            Err(e) => last_err = Some(e),
+        }
+    }
+
+    if found.len() > 1 {
+        let signatures = found
+            .iter()
+            .map(|(_, f, _)| f.signature())
+            .collect::<Vec<_>>();
+        return Err(Error::Abi(format!(
+            "{} is ambiguous, call one of {} by signature",
+            name,
+            signatures.join(", ")
+        )));
+    }
+    found.pop().ok_or_else(|| {
+        last_err.unwrap_or_else(|| Error::Abi(format!("function {} is not in the abi", name)))
+    })
 }
 
 #[cfg(test)]
 mod test {
     use num_bigint::BigInt;
+    use serde_json::json;
 
-    use crate::client::get_client;
+    use super::{CallOutput, check_call_value, resolve_function};
+    use crate::{abi::JsonAbi, client::get_client};
+
+    #[test]
+    fn test_resolve_function() {
+        let abi = JsonAbi::from_json(
+            r#"[
+                {"type":"function","name":"balanceOf","inputs":[{"name":"owner","type":"address"}],"outputs":[{"type":"uint256"}],"stateMutability":"view"},
+                {"type":"function","name":"mint","inputs":[{"name":"to","type":"address"}],"outputs":[],"stateMutability":"nonpayable"},
+                {"type":"function","name":"mint","inputs":[{"name":"to","type":"address"},{"name":"amount","type":"uint256"}],"outputs":[],"stateMutability":"nonpayable"},
+                {"type":"function","name":"set","inputs":[{"name":"value","type":"uint256"}],"outputs":[],"stateMutability":"nonpayable"},
+                {"type":"function","name":"set","inputs":[{"name":"value","type":"string"}],"outputs":[],"stateMutability":"nonpayable"}
+            ]"#,
+        )
+        .unwrap();
+        let owner = json!("TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t");
+
+        let (entry, function, _) =
+            resolve_function(&abi, "balanceOf", std::slice::from_ref(&owner)).unwrap();
+        assert!(entry.is_constant());
+        assert_eq!(function.signature(), "balanceOf(address)");
+        // overloads are resolved by the argument count, then the argument types
+        let (_, function, _) = resolve_function(&abi, "mint", &[owner.clone(), json!(1)]).unwrap();
+        assert_eq!(function.signature(), "mint(address,uint256)");
+        let (_, function, _) = resolve_function(&abi, "set", &[json!("text")]).unwrap();
+        assert_eq!(function.signature(), "set(string)");
+        assert!(resolve_function(&abi, "set", &[json!("1")]).is_err());
+        let (_, function, _) = resolve_function(&abi, "set(uint)", &[json!("1")]).unwrap();
+        assert_eq!(function.signature(), "set(uint256)");
+
+        assert!(resolve_function(&abi, "balanceOf", &[]).is_err());
+        assert!(resolve_function(&abi, "balanceOf", &[json!(1)]).is_err());
+        assert!(resolve_function(&abi, "transfer", &[]).is_err());
+    }
+
+    #[test]
+    fn test_check_call_value() {
+        let abi = JsonAbi::from_json(
+            r#"[
+                {"type":"function","name":"deposit","inputs":[],"outputs":[],"stateMutability":"payable"},
+                {"type":"function","name":"withdraw","inputs":[],"outputs":[],"stateMutability":"nonpayable"},
+                {"type":"function","name":"legacy","inputs":[],"outputs":[],"payable":true}
+            ]"#,
+        )
+        .unwrap();
+        let check = |name: &str, call_value: i64| {
+            let (entry, function, _) = resolve_function(&abi, name, &[]).unwrap();
+            check_call_value(entry, &function, call_value)
+        };
+        assert!(check("deposit", 1_000_000).is_ok());
+        assert!(check("legacy", 1_000_000).is_ok());
+        assert!(check("withdraw", 0).is_ok());
+        assert!(
+            check("withdraw", 1)
+                .unwrap_err()
+                .to_string()
+                .contains("withdraw() is not payable")
+        );
+        assert!(check("deposit", -1).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_call_by_name() {
+        let mut cli = get_client().await;
+        let output = cli
+            .call_by_name(
+                None,
+                "TTvFE3RWS48yNhS6EaK3SGbBYycaEmV7Pw",
+                "balanceOf",
+                &[json!("TD614vXXu2qH3VrMnkeKJSW4q7FUMVstar")],
+                0,
+                0,
+            )
+            .await
+            .expect("call balanceOf err");
+        match output {
+            CallOutput::Constant(outputs) => {
+                assert_eq!(
+                    outputs["0"],
+                    json!(BigInt::from(123455e6 as i64).to_string())
+                )
+            }
+            CallOutput::Transaction(_) => panic!("balanceOf is constant"),
+        }
+    }
 
     #[tokio::test]
     async fn test_trc20_balance() {
@@ -1,29 +1,72 @@
-use crate::tron::protocol::wallet_client::WalletClient;
+use std::{collections::HashMap, sync::Arc};
+
+use tonic::transport::Channel;
+
+use crate::{
+    Error,
+    abi::JsonAbi,
+    tron::protocol::{wallet_client::WalletClient, wallet_solidity_client::WalletSolidityClient},
+};
 
 #[derive(Debug, Clone)]
 pub struct GrpcClient {
-    inner: WalletClient<tonic::transport::Channel>,
+    inner: WalletClient<Channel>,
+    solidity: Option<WalletSolidityClient<Channel>>,
+    pbft: Option<WalletSolidityClient<Channel>>,
+    abi_cache: HashMap<Address, Arc<JsonAbi>>,
 }
 
 mod common;
-pub use common::CreatedAccount;
 pub use common::Address;
+pub use common::CreatedAccount;
 
 mod account;
 pub use account::AccountResourceBalance;
 
+mod builder;
+pub use builder::{BlockRef, ContractMessage, TransactionBuilder};
+
+mod confirm;
+pub use confirm::{ConfirmOptions, Confirmation};
+
 mod contract;
+pub use contract::CallOutput;
+
+mod transfer;
 
 impl GrpcClient {
-    pub async fn new(endpoint: &str) -> Result<Self, tonic::transport::Error> {
+    pub async fn new(endpoint: &str) -> Result<Self, Error> {
         Ok(Self {
             inner: WalletClient::connect(endpoint.to_string()).await?,
+            solidity: None,
+            pbft: None,
+            abi_cache: HashMap::new(),
         })
     }
 
-    pub fn client(&mut self) -> &mut WalletClient<tonic::transport::Channel> {
+    /// Connects the solidity node gRPC endpoint(`WalletSolidity` service).
+    pub async fn with_solidity(mut self, endpoint: &str) -> Result<Self, Error> {
+        self.solidity = Some(WalletSolidityClient::connect(endpoint.to_string()).await?);
+        Ok(self)
+    }
+
+    /// Connects the PBFT gRPC endpoint(`WalletSolidity` service serving PBFT-finalized data).
+    pub async fn with_pbft(mut self, endpoint: &str) -> Result<Self, Error> {
+        self.pbft = Some(WalletSolidityClient::connect(endpoint.to_string()).await?);
+        Ok(self)
+    }
+
+    pub fn client(&mut self) -> &mut WalletClient<Channel> {
         &mut self.inner
     }
+
+    pub fn solidity_client(&mut self) -> Option<&mut WalletSolidityClient<Channel>> {
+        self.solidity.as_mut()
+    }
+
+    pub fn pbft_client(&mut self) -> Option<&mut WalletSolidityClient<Channel>> {
+        self.pbft.as_mut()
+    }
 }
 
 #[cfg(test)]
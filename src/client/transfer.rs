@@ -0,0 +1,94 @@
+use tonic::{Request, Response};
+
+use crate::{
+    Error,
+    client::GrpcClient,
+    signer::Signer,
+    tron::protocol::{Transaction, TransactionExtention, TransferContract},
+};
+
+impl GrpcClient {
+    /// Creates a TRX transfer(`TransferContract`) transaction, `amount` is in sun(1 TRX = 1e6 sun).
+    pub async fn trx_transfer(
+        &mut self,
+        from: &str,
+        to: &str,
+        amount: i64,
+        memo: Option<&str>,
+    ) -> Result<Response<TransactionExtention>, Error> {
+        if amount <= 0 {
+            return Err(Error::InvalidArgument("amount is not positive".to_string()));
+        }
+
+        let req = Request::new(TransferContract {
+            owner_address: Self::parse_address(from)?.into_inner(),
+            to_address: Self::parse_address(to)?.into_inner(),
+            amount,
+        });
+
+        let mut resp = self.inner.create_transaction2(req).await?;
+        let ext = resp.get_mut();
+        if let Some(e) = ext.result.as_ref().and_then(Error::from_return) {
+            return Err(e);
+        }
+        if let Some(raw) = ext.transaction.as_mut().and_then(|tx| tx.raw_data.as_mut()) {
+            if let Some(memo) = memo {
+                raw.data = memo.as_bytes().to_vec();
+            }
+            ext.txid = Self::get_tx_hash(raw);
+        }
+        Ok(resp)
+    }
+
+    /// Creates a TRX transfer from the signer's account, signed and ready to broadcast.
+    pub async fn signed_trx_transfer(
+        &mut self,
+        signer: &dyn Signer,
+        to: &str,
+        amount: i64,
+        memo: Option<&str>,
+    ) -> Result<Transaction, Error> {
+        let from = signer.address().to_bs58();
+        let ext = self.trx_transfer(&from, to, amount, memo).await?;
+        Self::sign_extention(ext.into_inner(), signer).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use prost::Message;
+
+    use crate::{
+        api::TransferContract,
+        client::{GrpcClient, get_client},
+    };
+
+    #[tokio::test]
+    async fn test_trx_transfer() {
+        let mut cli = get_client().await;
+        let ext = cli
+            .trx_transfer(
+                "TXwUd9ywscLUZQcP5tPfqU266kbh3QmYxx",
+                "TD614vXXu2qH3VrMnkeKJSW4q7FUMVstar",
+                1,
+                Some("memo"),
+            )
+            .await
+            .expect("create trx transfer tx err")
+            .into_inner();
+
+        let raw = ext
+            .transaction
+            .and_then(|tx| tx.raw_data)
+            .expect("transaction raw data");
+        assert_eq!(raw.data, b"memo");
+        assert_eq!(ext.txid, GrpcClient::get_tx_hash(&raw));
+
+        let param = raw.contract[0]
+            .parameter
+            .as_ref()
+            .expect("contract parameter");
+        let transfer = TransferContract::decode(param.value.as_slice()).expect("decode transfer");
+        assert_eq!(transfer.amount, 1);
+    }
+}
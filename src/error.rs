@@ -0,0 +1,96 @@
+use crate::api::{Return, r#return::ResponseCode};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("transport error: {0}")]
+    Transport(#[from] tonic::transport::Error),
+
+    #[error("rpc error: {0}")]
+    Rpc(#[from] tonic::Status),
+
+    #[error("invalid address: {0}")]
+    Address(String),
+
+    #[error("invalid key: {0}")]
+    Key(String),
+
+    #[error("signer error: {0}")]
+    Signer(String),
+
+    #[error("abi error: {0}")]
+    Abi(String),
+
+    /// The node rejected a transaction, see `Return.code` and `Return.message`.
+    #[error("broadcast rejected({}): {message}", code.as_str_name())]
+    Broadcast { code: ResponseCode, message: String },
+
+    /// The transaction expired before inclusion and could not be rebuilt any more.
+    #[error("transaction expired: {txid}")]
+    Expired { txid: String },
+
+    #[error("transaction not confirmed before timeout: {txid}")]
+    Timeout { txid: String },
+
+    /// A Sign-In-With-TRON message or signature was rejected.
+    #[error("authentication failed: {0}")]
+    Auth(String),
+
+    #[error("invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("unexpected response: {0}")]
+    UnexpectedResponse(String),
+}
+
+impl Error {
+    /// Returns the broadcast rejection carried by `ret`, or `None` if it reports success.
+    pub fn from_return(ret: &Return) -> Option<Self> {
+        if ret.result {
+            return None;
+        }
+        Some(Self::Broadcast {
+            code: ResponseCode::try_from(ret.code).unwrap_or(ResponseCode::OtherError),
+            message: String::from_utf8_lossy(&ret.message).into_owned(),
+        })
+    }
+
+    pub fn broadcast_code(&self) -> Option<ResponseCode> {
+        match self {
+            Self::Broadcast { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_return() {
+        assert!(
+            Error::from_return(&Return {
+                result: true,
+                ..Default::default()
+            })
+            .is_none()
+        );
+
+        let err = Error::from_return(&Return {
+            result: false,
+            code: ResponseCode::DupTransactionError as i32,
+            message: b"dup transaction".to_vec(),
+        })
+        .expect("rejected return");
+        assert_eq!(
+            err.broadcast_code(),
+            Some(ResponseCode::DupTransactionError)
+        );
+        assert_eq!(
+            err.to_string(),
+            "broadcast rejected(DUP_TRANSACTION_ERROR): dup transaction"
+        );
+    }
+}
@@ -1,4 +1,11 @@
+pub mod abi;
 pub mod client;
+mod error;
 mod tron;
+pub use error::{Error, Result};
 pub use tron::protocol as api;
+pub mod multisig;
+pub mod signer;
+pub mod siwt;
 pub mod utils;
+pub mod wallet;
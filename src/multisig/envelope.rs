@@ -0,0 +1,423 @@
+use std::{fmt, str::FromStr};
+
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    Error,
+    api::{Account, Transaction, transaction},
+    client::{Address, GrpcClient},
+    multisig::evaluate_sign_weight,
+    signer::Signer,
+    utils::{bs58, crypto},
+};
+
+const VERSION: u32 = 1;
+const TEXT_PREFIX: &str = "pstx:";
+
+/// A signature collected by a `PartiallySignedTransaction`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectedSignature {
+    pub signer: Address,
+    pub signature: Vec<u8>,
+}
+
+/// A transaction passed between parties(files, QR codes) to collect signatures.
+///
+/// Every signature is verified against the txid when it is added or decoded, so an envelope
+/// only ever holds signatures of its own transaction. `Display`/`FromStr` convert the compact
+/// text encoding(`pstx:` + base58check of the protobuf envelope), serde uses readable JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "EnvelopeJson", into = "EnvelopeJson")]
+pub struct PartiallySignedTransaction {
+    raw_data: transaction::Raw,
+    txid: Vec<u8>,
+    permission_id: i32,
+    signatures: Vec<CollectedSignature>,
+}
+
+impl PartiallySignedTransaction {
+    /// Wraps an unsigned or partially signed transaction, existing signatures are kept.
+    pub fn new(tx: Transaction) -> Result<Self, Error> {
+        let raw_data = tx
+            .raw_data
+            .ok_or_else(|| Error::InvalidArgument("transaction raw data is empty".to_string()))?;
+        let permission_id = raw_data
+            .contract
+            .first()
+            .ok_or_else(|| Error::InvalidArgument("transaction contract is empty".to_string()))?
+            .permission_id;
+        let mut envelope = Self {
+            txid: GrpcClient::get_tx_hash(&raw_data),
+            raw_data,
+            permission_id,
+            signatures: Vec::new(),
+        };
+        for sig in tx.signature {
+            envelope.add_signature(sig)?;
+        }
+        Ok(envelope)
+    }
+
+    pub fn txid(&self) -> &[u8] {
+        &self.txid
+    }
+
+    pub fn raw_data(&self) -> &transaction::Raw {
+        &self.raw_data
+    }
+
+    pub fn permission_id(&self) -> i32 {
+        self.permission_id
+    }
+
+    /// Expiration time of the transaction in milliseconds.
+    pub fn expiration(&self) -> i64 {
+        self.raw_data.expiration
+    }
+
+    pub fn is_expired(&self, now_millis: i64) -> bool {
+        now_millis >= self.expiration()
+    }
+
+    pub fn signatures(&self) -> &[CollectedSignature] {
+        &self.signatures
+    }
+
+    pub fn signers(&self) -> Vec<Address> {
+        self.signatures.iter().map(|s| s.signer).collect()
+    }
+
+    /// Adds a signature of the txid and returns its signer. A signer which already signed is
+    /// kept with its first signature.
+    pub fn add_signature(&mut self, signature: Vec<u8>) -> Result<Address, Error> {
+        let signer = crypto::recover_address(&self.txid, &signature)?;
+        if !self.signatures.iter().any(|s| s.signer == signer) {
+            self.signatures
+                .push(CollectedSignature { signer, signature });
+        }
+        Ok(signer)
+    }
+
+    pub async fn sign(&mut self, signer: &dyn Signer) -> Result<(), Error> {
+        let sig = signer.sign_hash(&self.txid).await?;
+        if crypto::recover_address(&self.txid, &sig)? != signer.address() {
+            return Err(Error::Signer(format!(
+                "signature is not signed by {}",
+                signer.address()
+            )));
+        }
+        self.add_signature(sig).map(|_| ())
+    }
+
+    /// Collects the signatures of another copy of the same transaction.
+    pub fn merge(&mut self, other: &Self) -> Result<(), Error> {
+        if self.txid != other.txid {
+            return Err(Error::InvalidArgument(format!(
+                "txid mismatch: {}, {}",
+                hex::encode(&self.txid),
+                hex::encode(&other.txid)
+            )));
+        }
+        for s in &other.signatures {
+            if !self.signatures.iter().any(|c| c.signer == s.signer) {
+                self.signatures.push(s.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// The transaction with the signatures collected so far.
+    pub fn to_transaction(&self) -> Transaction {
+        Transaction {
+            raw_data: Some(self.raw_data.clone()),
+            signature: self
+                .signatures
+                .iter()
+                .map(|s| s.signature.clone())
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// The transaction ready to broadcast, after checking the signatures reach the threshold
+    /// of the selected permission of `account`, the owner account.
+    pub fn finalize(self, account: &Account) -> Result<Transaction, Error> {
+        let tx = self.to_transaction();
+        let weight = evaluate_sign_weight(&tx, account)?;
+        if !weight.is_enough() {
+            return Err(Error::InvalidArgument(format!(
+                "not enough signatures: weight {}/{}, operation allowed: {}, unauthorized: {:?}",
+                weight.weight,
+                weight.threshold(),
+                weight.operation_allowed,
+                weight.unauthorized
+            )));
+        }
+        Ok(tx)
+    }
+
+    fn from_parts(
+        raw_data: transaction::Raw,
+        txid: Vec<u8>,
+        permission_id: i32,
+        signatures: Vec<(Address, Vec<u8>)>,
+    ) -> Result<Self, Error> {
+        let mut envelope = Self::new(Transaction {
+            raw_data: Some(raw_data),
+            ..Default::default()
+        })?;
+        if envelope.txid != txid {
+            return Err(Error::InvalidArgument(
+                "envelope txid mismatch raw data".to_string(),
+            ));
+        }
+        if envelope.permission_id != permission_id {
+            return Err(Error::InvalidArgument(
+                "envelope permission id mismatch raw data".to_string(),
+            ));
+        }
+        for (signer, sig) in signatures {
+            if envelope.add_signature(sig)? != signer {
+                return Err(Error::InvalidArgument(format!(
+                    "envelope signature is not signed by {}",
+                    signer
+                )));
+            }
+        }
+        Ok(envelope)
+    }
+}
+
+impl fmt::Display for PartiallySignedTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut data = EnvelopeProto {
+            version: VERSION,
+            raw_data: Some(self.raw_data.clone()),
+            txid: self.txid.clone(),
+            permission_id: self.permission_id,
+            signatures: self
+                .signatures
+                .iter()
+                .map(|s| SignatureProto {
+                    signer: s.signer.into_inner(),
+                    signature: s.signature.clone(),
+                })
+                .collect(),
+            expiration: self.expiration(),
+        }
+        .encode_to_vec();
+        let checksum = Sha256::digest(Sha256::digest(&data));
+        data.extend_from_slice(&checksum[..4]);
+        write!(f, "{}{}", TEXT_PREFIX, bs58::encode(data))
+    }
+}
+
+impl FromStr for PartiallySignedTransaction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |e: String| Error::InvalidArgument(format!("invalid envelope: {}", e));
+        let data = bs58::decode(
+            s.trim()
+                .strip_prefix(TEXT_PREFIX)
+                .ok_or_else(|| invalid("missing prefix".to_string()))?,
+        )
+        .map_err(|e| invalid(e.to_string()))?;
+        if data.len() < 4 {
+            return Err(invalid("too short".to_string()));
+        }
+        let (data, checksum) = data.split_at(data.len() - 4);
+        if Sha256::digest(Sha256::digest(data))[..4] != *checksum {
+            return Err(invalid("checksum mismatch".to_string()));
+        }
+
+        let proto = EnvelopeProto::decode(data).map_err(|e| invalid(e.to_string()))?;
+        if proto.version != VERSION {
+            return Err(invalid(format!("unsupported version {}", proto.version)));
+        }
+        let signatures = proto
+            .signatures
+            .into_iter()
+            .map(|s| Ok((Address::from_bytes(&s.signer)?, s.signature)))
+            .collect::<Result<_, Error>>()?;
+        Self::from_parts(
+            proto
+                .raw_data
+                .ok_or_else(|| invalid("raw data is empty".to_string()))?,
+            proto.txid,
+            proto.permission_id,
+            signatures,
+        )
+    }
+}
+
+// compact encoding, `expiration` duplicates the raw data for readers of the envelope
+#[derive(Clone, PartialEq, Message)]
+struct EnvelopeProto {
+    #[prost(uint32, tag = "1")]
+    version: u32,
+    #[prost(message, optional, tag = "2")]
+    raw_data: Option<transaction::Raw>,
+    #[prost(bytes = "vec", tag = "3")]
+    txid: Vec<u8>,
+    #[prost(int32, tag = "4")]
+    permission_id: i32,
+    #[prost(message, repeated, tag = "5")]
+    signatures: Vec<SignatureProto>,
+    #[prost(int64, tag = "6")]
+    expiration: i64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct SignatureProto {
+    #[prost(bytes = "vec", tag = "1")]
+    signer: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    signature: Vec<u8>,
+}
+
+// JSON encoding: bytes in hex, addresses in base58
+#[derive(Serialize, Deserialize)]
+struct EnvelopeJson {
+    version: u32,
+    txid: String,
+    /// protobuf encoded `transaction::Raw`
+    raw_data: String,
+    permission_id: i32,
+    expiration: i64,
+    signatures: Vec<SignatureJson>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SignatureJson {
+    signer: String,
+    signature: String,
+}
+
+impl From<PartiallySignedTransaction> for EnvelopeJson {
+    fn from(envelope: PartiallySignedTransaction) -> Self {
+        Self {
+            version: VERSION,
+            txid: hex::encode(&envelope.txid),
+            raw_data: hex::encode(envelope.raw_data.encode_to_vec()),
+            permission_id: envelope.permission_id,
+            expiration: envelope.expiration(),
+            signatures: envelope
+                .signatures
+                .iter()
+                .map(|s| SignatureJson {
+                    signer: s.signer.to_bs58(),
+                    signature: hex::encode(&s.signature),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<EnvelopeJson> for PartiallySignedTransaction {
+    type Error = Error;
+
+    fn try_from(json: EnvelopeJson) -> Result<Self, Self::Error> {
+        let invalid = |e: String| Error::InvalidArgument(format!("invalid envelope: {}", e));
+        if json.version != VERSION {
+            return Err(invalid(format!("unsupported version {}", json.version)));
+        }
+        let raw_data = hex::decode(&json.raw_data).map_err(|e| invalid(e.to_string()))?;
+        let raw_data =
+            transaction::Raw::decode(raw_data.as_slice()).map_err(|e| invalid(e.to_string()))?;
+        let signatures = json
+            .signatures
+            .into_iter()
+            .map(|s| {
+                Ok((
+                    s.signer.parse()?,
+                    hex::decode(&s.signature).map_err(|e| invalid(e.to_string()))?,
+                ))
+            })
+            .collect::<Result<_, Error>>()?;
+        Self::from_parts(
+            raw_data,
+            hex::decode(&json.txid).map_err(|e| invalid(e.to_string()))?,
+            json.permission_id,
+            signatures,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        api::{
+            Account, Key, Permission, permission::PermissionType,
+            transaction::contract::ContractType,
+        },
+        multisig::{OperationSet, PartiallySignedTransaction, test_transfer as transfer},
+        signer::{LocalSigner, Signer},
+    };
+
+    #[tokio::test]
+    async fn test_envelope() {
+        let owner = LocalSigner::random();
+        let (a, b) = (LocalSigner::random(), LocalSigner::random());
+        let account = Account {
+            address: owner.address().into_inner(),
+            active_permission: vec![Permission {
+                r#type: PermissionType::Active as i32,
+                id: 2,
+                threshold: 2,
+                operations: OperationSet::from_iter([ContractType::TransferContract]).into(),
+                keys: [&a, &b]
+                    .iter()
+                    .map(|s| Key {
+                        address: s.address().into_inner(),
+                        weight: 1,
+                    })
+                    .collect(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let coordinator = PartiallySignedTransaction::new(transfer(&owner, 2)).unwrap();
+        assert_eq!(coordinator.permission_id(), 2);
+        assert_eq!(coordinator.expiration(), 1_700_000_060_000);
+        assert!(coordinator.is_expired(1_700_000_060_000));
+        let text = coordinator.to_string();
+        assert!(text.starts_with("pstx:"));
+
+        // each party signs its own copy
+        let mut copy_a: PartiallySignedTransaction = text.parse().unwrap();
+        copy_a.sign(&a).await.unwrap();
+        let mut copy_b: PartiallySignedTransaction = text.parse().unwrap();
+        copy_b.sign(&b).await.unwrap();
+        copy_b.sign(&b).await.unwrap();
+
+        let mut merged: PartiallySignedTransaction = copy_a.to_string().parse().unwrap();
+        assert!(merged.clone().finalize(&account).is_err());
+        let json = serde_json::to_string(&copy_b).unwrap();
+        merged.merge(&serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(merged.signers(), vec![a.address(), b.address()]);
+
+        let tx = merged.finalize(&account).expect("finalize err");
+        assert_eq!(tx.signature.len(), 2);
+        assert_eq!(
+            PartiallySignedTransaction::new(tx).unwrap().signers().len(),
+            2
+        );
+
+        // other transactions and tampered envelopes are rejected
+        let other = PartiallySignedTransaction::new(transfer(&owner, 2)).unwrap();
+        assert!(copy_a.clone().merge(&other).is_err());
+        assert!(copy_a.add_signature(vec![0; 65]).is_err());
+        let mut text = copy_a.to_string();
+        let last = if text.pop() == Some('1') { '2' } else { '1' };
+        text.push(last);
+        assert!(text.parse::<PartiallySignedTransaction>().is_err());
+        let json = serde_json::to_string(&copy_a)
+            .unwrap()
+            .replace(&a.address().to_bs58(), &b.address().to_bs58());
+        assert!(serde_json::from_str::<PartiallySignedTransaction>(&json).is_err());
+    }
+}
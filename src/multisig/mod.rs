@@ -0,0 +1,204 @@
+//! Offline checks and signature collection of multi-signature transactions.
+
+use prost::Message;
+
+use crate::{
+    Error,
+    api::{
+        Account, Key, Permission, Transaction,
+        permission::PermissionType,
+        transaction::{self, contract::ContractType},
+    },
+    client::Address,
+};
+
+mod envelope;
+pub use envelope::{CollectedSignature, PartiallySignedTransaction};
+mod operations;
+pub use operations::OperationSet;
+mod signers;
+pub use signers::{SignerCheck, recover_signers, verify_signers};
+mod update;
+pub use update::{
+    MAX_ACTIVE_PERMISSIONS, MAX_KEYS, PermissionChange, PermissionSpec, PermissionUpdate,
+};
+mod weight;
+pub use weight::{SignWeight, evaluate_sign_weight};
+
+/// The single contract of a transaction.
+pub fn transaction_contract(tx: &Transaction) -> Result<&transaction::Contract, Error> {
+    tx.raw_data
+        .as_ref()
+        .and_then(|raw| raw.contract.first())
+        .ok_or_else(|| Error::InvalidArgument("transaction contract is empty".to_string()))
+}
+
+// `owner_address` is field 2 of the contracts listed in `contract_owner`, field 1 of the others.
+// Each stub decodes one field, the other fields(of any wire type) are skipped.
+#[derive(Clone, PartialEq, Message)]
+struct OwnerField1 {
+    #[prost(bytes = "vec", tag = "1")]
+    owner_address: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct OwnerField2 {
+    #[prost(bytes = "vec", tag = "2")]
+    owner_address: Vec<u8>,
+}
+
+/// The `owner_address` of a system contract.
+pub fn contract_owner(contract: &transaction::Contract) -> Result<Address, Error> {
+    let any = contract
+        .parameter
+        .as_ref()
+        .ok_or_else(|| Error::InvalidArgument("contract parameter is empty".to_string()))?;
+    let decode_err = |e: prost::DecodeError| {
+        Error::InvalidArgument(format!("decode contract parameter err: {}", e))
+    };
+    let owner = match ContractType::try_from(contract.r#type) {
+        Ok(
+            ContractType::AccountUpdateContract
+            | ContractType::SetAccountIdContract
+            | ContractType::TransferAssetContract,
+        ) => {
+            OwnerField2::decode(any.value.as_slice())
+                .map_err(decode_err)?
+                .owner_address
+        }
+        Ok(ContractType::ShieldedTransferContract) | Err(_) => {
+            return Err(Error::InvalidArgument(format!(
+                "contract type {} has no owner address",
+                contract.r#type
+            )));
+        }
+        Ok(_) => {
+            OwnerField1::decode(any.value.as_slice())
+                .map_err(decode_err)?
+                .owner_address
+        }
+    };
+    Address::from_bytes(&owner)
+}
+
+/// The permission with `id`, like the node resolves a contract `permission_id`: an account
+/// without an explicit owner(or witness) permission is controlled by its own key alone.
+pub fn permission_by_id(account: &Account, id: i32) -> Option<Permission> {
+    let default = |ty: PermissionType, name: &str| Permission {
+        r#type: ty as i32,
+        id,
+        permission_name: name.to_string(),
+        threshold: 1,
+        keys: vec![Key {
+            address: account.address.clone(),
+            weight: 1,
+        }],
+        ..Default::default()
+    };
+    match id {
+        0 => Some(
+            account
+                .owner_permission
+                .clone()
+                .unwrap_or_else(|| default(PermissionType::Owner, "owner")),
+        ),
+        1 if account.is_witness => Some(
+            account
+                .witness_permission
+                .clone()
+                .unwrap_or_else(|| default(PermissionType::Witness, "witness")),
+        ),
+        1 => None,
+        _ => account
+            .active_permission
+            .iter()
+            .find(|p| p.id == id)
+            .cloned(),
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn test_transfer(owner: &crate::signer::LocalSigner, permission_id: i32) -> Transaction {
+    use crate::{
+        api::TransferContract,
+        client::{BlockRef, TransactionBuilder},
+        signer::{LocalSigner, Signer},
+    };
+
+    TransactionBuilder::new(BlockRef {
+        number: 1,
+        hash: [0; 32],
+        timestamp: 0,
+    })
+    .contract(&TransferContract {
+        owner_address: owner.address().into_inner(),
+        to_address: LocalSigner::random().address().into_inner(),
+        amount: 1_000_000,
+    })
+    .timestamp(1_700_000_000_000)
+    .permission_id(permission_id)
+    .build()
+    .expect("build transfer err")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        api::{AccountUpdateContract, FreezeBalanceV2Contract, TransferContract},
+        client::{BlockRef, TransactionBuilder},
+        multisig::{contract_owner, transaction_contract},
+        signer::{LocalSigner, Signer},
+    };
+
+    #[test]
+    fn test_contract_owner() {
+        let owner = LocalSigner::random().address();
+        let builder = TransactionBuilder::new(BlockRef {
+            number: 1,
+            hash: [0; 32],
+            timestamp: 0,
+        });
+
+        let tx = builder
+            .clone()
+            .contract(&TransferContract {
+                owner_address: owner.into_inner(),
+                to_address: LocalSigner::random().address().into_inner(),
+                amount: 1,
+            })
+            .build()
+            .unwrap();
+        assert_eq!(
+            contract_owner(transaction_contract(&tx).unwrap()).unwrap(),
+            owner
+        );
+
+        // field 2 is a varint
+        let tx = builder
+            .clone()
+            .contract(&FreezeBalanceV2Contract {
+                owner_address: owner.into_inner(),
+                frozen_balance: 1_000_000,
+                resource: 1,
+            })
+            .build()
+            .unwrap();
+        assert_eq!(
+            contract_owner(transaction_contract(&tx).unwrap()).unwrap(),
+            owner
+        );
+
+        // owner_address is field 2
+        let tx = builder
+            .contract(&AccountUpdateContract {
+                account_name: b"name".to_vec(),
+                owner_address: owner.into_inner(),
+            })
+            .build()
+            .unwrap();
+        assert_eq!(
+            contract_owner(transaction_contract(&tx).unwrap()).unwrap(),
+            owner
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use std::fmt;
+
+use crate::{Error, api::transaction::contract::ContractType};
+
+/// The `Permission.operations` bitmask: bit `n`(byte `n / 8`, bit `n % 8`) allows the
+/// contract type with value `n`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OperationSet([u8; 32]);
+
+impl OperationSet {
+    pub const LEN: usize = 32;
+
+    pub const fn empty() -> Self {
+        Self([0; Self::LEN])
+    }
+
+    /// Every contract type known to this SDK.
+    pub fn all() -> Self {
+        (0..Self::LEN as i32 * 8)
+            .filter_map(|n| ContractType::try_from(n).ok())
+            .collect()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        bytes.try_into().map(Self).map_err(|_| {
+            Error::InvalidArgument(format!(
+                "invalid operations length: get:{}, expect: {}",
+                bytes.len(),
+                Self::LEN
+            ))
+        })
+    }
+
+    pub fn from_hex(operations: &str) -> Result<Self, Error> {
+        let bytes = hex::decode(operations)
+            .map_err(|e| Error::InvalidArgument(format!("invalid operations hex: {}", e)))?;
+        Self::from_bytes(&bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    pub fn contains(&self, ty: ContractType) -> bool {
+        self.contains_value(ty as i32)
+    }
+
+    /// Like `contains`, for a raw `Contract.type` value.
+    pub fn contains_value(&self, ty: i32) -> bool {
+        usize::try_from(ty)
+            .ok()
+            .and_then(|n| self.0.get(n / 8).map(|b| b & (1 << (n % 8)) != 0))
+            .unwrap_or(false)
+    }
+
+    pub fn insert(&mut self, ty: ContractType) {
+        let n = ty as usize;
+        self.0[n / 8] |= 1 << (n % 8);
+    }
+
+    pub fn remove(&mut self, ty: ContractType) {
+        let n = ty as usize;
+        self.0[n / 8] &= !(1 << (n % 8));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|b| *b == 0)
+    }
+
+    /// The allowed contract types, bits of unknown types are skipped.
+    pub fn iter(&self) -> impl Iterator<Item = ContractType> + '_ {
+        (0..Self::LEN as i32 * 8)
+            .filter(|n| self.contains_value(*n))
+            .filter_map(|n| ContractType::try_from(n).ok())
+    }
+
+    // bits without a known contract type
+    fn unknown(&self) -> impl Iterator<Item = i32> + '_ {
+        (0..Self::LEN as i32 * 8)
+            .filter(|n| self.contains_value(*n) && ContractType::try_from(*n).is_err())
+    }
+}
+
+impl TryFrom<&[u8]> for OperationSet {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl TryFrom<Vec<u8>> for OperationSet {
+    type Error = Error;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::from_bytes(&bytes)
+    }
+}
+
+impl From<OperationSet> for Vec<u8> {
+    fn from(operations: OperationSet) -> Self {
+        operations.0.to_vec()
+    }
+}
+
+impl FromIterator<ContractType> for OperationSet {
+    fn from_iter<T: IntoIterator<Item = ContractType>>(iter: T) -> Self {
+        let mut operations = Self::empty();
+        operations.extend(iter);
+        operations
+    }
+}
+
+impl Extend<ContractType> for OperationSet {
+    fn extend<T: IntoIterator<Item = ContractType>>(&mut self, iter: T) {
+        for ty in iter {
+            self.insert(ty);
+        }
+    }
+}
+
+/// e.g. `TransferContract, TriggerSmartContract, #60`, unknown types are shown by value.
+impl fmt::Display for OperationSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = self
+            .iter()
+            .map(|ty| ty.as_str_name().to_string())
+            .chain(self.unknown().map(|n| format!("#{}", n)))
+            .collect::<Vec<_>>();
+        if names.is_empty() {
+            return f.write_str("(none)");
+        }
+        f.write_str(&names.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::OperationSet;
+    use crate::api::transaction::contract::ContractType;
+
+    #[test]
+    fn test_operation_set() {
+        // the default active permission operations of TronLink
+        let ops = OperationSet::from_hex(
+            "7fff1fc0033e0000000000000000000000000000000000000000000000000000",
+        )
+        .unwrap();
+        assert!(ops.contains(ContractType::TransferContract));
+        assert!(ops.contains(ContractType::TriggerSmartContract));
+        assert!(ops.contains(ContractType::UpdateEnergyLimitContract));
+        assert!(!ops.contains(ContractType::AccountPermissionUpdateContract));
+        assert!(!ops.contains_value(7));
+        assert_eq!(ops.iter().count(), 29);
+        assert_eq!(ops.iter().collect::<OperationSet>(), ops);
+
+        let mut ops: OperationSet = [
+            ContractType::TransferContract,
+            ContractType::TriggerSmartContract,
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(ops.to_string(), "TransferContract, TriggerSmartContract");
+        let mut bytes: Vec<u8> = ops.into();
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(&bytes[..4], &[0b10, 0, 0, 0b1000_0000]);
+
+        bytes[7] |= 0b1_0000;
+        assert_eq!(
+            OperationSet::try_from(bytes).unwrap().to_string(),
+            "TransferContract, TriggerSmartContract, #60"
+        );
+
+        ops.remove(ContractType::TransferContract);
+        ops.remove(ContractType::TriggerSmartContract);
+        assert!(ops.is_empty());
+        assert_eq!(ops.to_string(), "(none)");
+        assert!(OperationSet::all().contains(ContractType::CancelAllUnfreezeV2Contract));
+        assert!(OperationSet::try_from(vec![0u8; 31]).is_err());
+    }
+}
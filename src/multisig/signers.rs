@@ -0,0 +1,144 @@
+use crate::{
+    Error,
+    api::{Account, Transaction},
+    client::{Address, GrpcClient},
+    multisig::{contract_owner, permission_by_id, transaction_contract},
+    utils::crypto,
+};
+
+/// The signer of one `Transaction.signature` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignerCheck {
+    pub address: Address,
+    /// The signer is the contract `owner_address`.
+    pub is_owner: bool,
+    /// Weight of the signer in the permission selected by the contract `permission_id`,
+    /// `None` if it is not a key of that permission.
+    pub weight: Option<i64>,
+}
+
+impl SignerCheck {
+    pub fn is_authorized(&self) -> bool {
+        self.weight.is_some()
+    }
+}
+
+/// Recovers the signer address of every signature, in order. The txid is recomputed from
+/// the raw data, so a tampered transaction recovers to unrelated addresses.
+pub fn recover_signers(tx: &Transaction) -> Result<Vec<Address>, Error> {
+    let raw = tx
+        .raw_data
+        .as_ref()
+        .ok_or_else(|| Error::InvalidArgument("transaction raw data is empty".to_string()))?;
+    let txid = GrpcClient::get_tx_hash(raw);
+    tx.signature
+        .iter()
+        .map(|sig| crypto::recover_address(&txid, sig))
+        .collect()
+}
+
+/// Checks every signer against the contract owner and the permission selected by the
+/// contract `permission_id` of `account`, which must be the owner account.
+pub fn verify_signers(tx: &Transaction, account: &Account) -> Result<Vec<SignerCheck>, Error> {
+    let contract = transaction_contract(tx)?;
+    let owner = contract_owner(contract)?;
+    if !account.address.is_empty() && Address::from_bytes(&account.address)? != owner {
+        return Err(Error::InvalidArgument(format!(
+            "account is not the contract owner {}",
+            owner
+        )));
+    }
+    let permission = permission_by_id(account, contract.permission_id).ok_or_else(|| {
+        Error::InvalidArgument(format!("permission {} not found", contract.permission_id))
+    })?;
+
+    Ok(recover_signers(tx)?
+        .into_iter()
+        .map(|address| SignerCheck {
+            address,
+            is_owner: address == owner,
+            weight: permission
+                .keys
+                .iter()
+                .find(|k| k.address == address.as_bytes())
+                .map(|k| k.weight),
+        })
+        .collect())
+}
+
+impl GrpcClient {
+    /// Fetches the contract owner account and checks every signer of `tx` against it.
+    pub async fn verify_transaction_signers(
+        &mut self,
+        tx: &Transaction,
+    ) -> Result<Vec<SignerCheck>, Error> {
+        let owner = contract_owner(transaction_contract(tx)?)?;
+        let account = self.get_account(&owner.to_bs58()).await?;
+        verify_signers(tx, &account)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        api::{Account, Key, Permission},
+        multisig::{recover_signers, test_transfer as transfer, verify_signers},
+        signer::{LocalSigner, Signer},
+    };
+
+    #[tokio::test]
+    async fn test_verify_signers() {
+        let owner = LocalSigner::random();
+        let (a, b, c) = (
+            LocalSigner::random(),
+            LocalSigner::random(),
+            LocalSigner::random(),
+        );
+        let account = Account {
+            address: owner.address().into_inner(),
+            active_permission: vec![Permission {
+                id: 2,
+                threshold: 2,
+                keys: [(&a, 1), (&b, 1)]
+                    .iter()
+                    .map(|(s, weight)| Key {
+                        address: s.address().into_inner(),
+                        weight: *weight,
+                    })
+                    .collect(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // default owner permission
+        let tx = owner.sign_transaction(transfer(&owner, 0)).await.unwrap();
+        let checks = verify_signers(&tx, &account).unwrap();
+        assert_eq!(checks.len(), 1);
+        assert!(checks[0].is_owner && checks[0].is_authorized());
+
+        let tx = transfer(&owner, 2);
+        let tx = a.sign_transaction(tx).await.unwrap();
+        let tx = c.sign_transaction(tx).await.unwrap();
+        assert_eq!(
+            recover_signers(&tx).unwrap(),
+            vec![a.address(), c.address()]
+        );
+        let checks = verify_signers(&tx, &account).unwrap();
+        assert_eq!(checks[0].weight, Some(1));
+        assert!(!checks[0].is_owner);
+        assert!(!checks[1].is_authorized());
+
+        // tampered raw data no longer recovers to the signer
+        let mut forged = tx.clone();
+        forged.raw_data.as_mut().unwrap().fee_limit = 1;
+        assert!(!verify_signers(&forged, &account).unwrap()[0].is_authorized());
+
+        assert!(verify_signers(&transfer(&owner, 3), &account).is_err());
+        let other = Account {
+            address: a.address().into_inner(),
+            ..Default::default()
+        };
+        assert!(verify_signers(&tx, &other).is_err());
+    }
+}
@@ -0,0 +1,533 @@
+use std::fmt;
+
+use tonic::{Request, Response};
+
+use crate::{
+    Error,
+    api::{
+        Account, AccountPermissionUpdateContract, Key, Permission, TransactionExtention,
+        permission::PermissionType,
+    },
+    client::{Address, GrpcClient},
+    multisig::{OperationSet, permission_by_id},
+};
+
+/// Chain limits checked by `PermissionUpdate::build`.
+pub const MAX_KEYS: usize = 5;
+pub const MAX_ACTIVE_PERMISSIONS: usize = 8;
+const MAX_NAME_LEN: usize = 32;
+
+/// One permission of a `PermissionUpdate`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionSpec {
+    pub name: String,
+    pub threshold: i64,
+    pub keys: Vec<(Address, i64)>,
+    /// Only used by active permissions.
+    pub operations: OperationSet,
+}
+
+impl PermissionSpec {
+    pub fn new(name: &str, threshold: i64) -> Self {
+        Self {
+            name: name.to_string(),
+            threshold,
+            keys: Vec::new(),
+            operations: OperationSet::empty(),
+        }
+    }
+
+    pub fn key(mut self, address: Address, weight: i64) -> Self {
+        self.keys.push((address, weight));
+        self
+    }
+
+    pub fn operations(mut self, operations: OperationSet) -> Self {
+        self.operations = operations;
+        self
+    }
+
+    fn to_permission(&self, ty: PermissionType, id: i32) -> Permission {
+        Permission {
+            r#type: ty as i32,
+            id,
+            permission_name: self.name.clone(),
+            threshold: self.threshold,
+            operations: match ty {
+                PermissionType::Active => self.operations.into(),
+                _ => Vec::new(),
+            },
+            keys: self
+                .keys
+                .iter()
+                .map(|(address, weight)| Key {
+                    address: address.into_inner(),
+                    weight: *weight,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn validate(&self, ty: PermissionType) -> Result<(), Error> {
+        let invalid = |msg: String| {
+            Err(Error::InvalidArgument(format!(
+                "{} permission {:?}: {}",
+                ty.as_str_name().to_lowercase(),
+                self.name,
+                msg
+            )))
+        };
+        if self.name.len() > MAX_NAME_LEN {
+            return invalid(format!("name is longer than {} bytes", MAX_NAME_LEN));
+        }
+        if self.keys.is_empty() || self.keys.len() > MAX_KEYS {
+            return invalid(format!("needs 1 to {} keys", MAX_KEYS));
+        }
+        if ty == PermissionType::Witness && self.keys.len() != 1 {
+            return invalid("needs exactly 1 key".to_string());
+        }
+        for (i, (address, weight)) in self.keys.iter().enumerate() {
+            if *weight <= 0 {
+                return invalid(format!("key {} weight is not positive", address));
+            }
+            if self.keys[..i].iter().any(|(a, _)| a == address) {
+                return invalid(format!("key {} is duplicated", address));
+            }
+        }
+        if self.threshold <= 0 {
+            return invalid("threshold is not positive".to_string());
+        }
+        let total = self
+            .keys
+            .iter()
+            .try_fold(0i64, |sum, (_, w)| sum.checked_add(*w));
+        if total.is_none_or(|total| total < self.threshold) {
+            return invalid(format!(
+                "threshold {} is not reachable by the key weights",
+                self.threshold
+            ));
+        }
+        if ty == PermissionType::Active && self.operations.is_empty() {
+            return invalid("operations are empty".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Builds an `AccountPermissionUpdateContract`, which replaces all permissions of an account.
+///
+/// The update is irreversible if the new owner keys are lost, so check `diff` against the
+/// current account before signing. The node charges an extra fee for the update.
+#[derive(Debug, Clone)]
+pub struct PermissionUpdate {
+    owner_address: Address,
+    owner: Option<PermissionSpec>,
+    witness: Option<PermissionSpec>,
+    actives: Vec<PermissionSpec>,
+}
+
+impl PermissionUpdate {
+    pub fn new(owner_address: Address) -> Self {
+        Self {
+            owner_address,
+            owner: None,
+            witness: None,
+            actives: Vec::new(),
+        }
+    }
+
+    pub fn owner(mut self, owner: PermissionSpec) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Only for witness accounts, the key signs the produced blocks.
+    pub fn witness(mut self, witness: PermissionSpec) -> Self {
+        self.witness = Some(witness);
+        self
+    }
+
+    /// Active permissions get the ids 2, 3, ... in the order they are added.
+    pub fn active(mut self, active: PermissionSpec) -> Self {
+        self.actives.push(active);
+        self
+    }
+
+    pub fn build(&self) -> Result<AccountPermissionUpdateContract, Error> {
+        let owner = self
+            .owner
+            .as_ref()
+            .ok_or_else(|| Error::InvalidArgument("owner permission is missing".to_string()))?;
+        owner.validate(PermissionType::Owner)?;
+        if let Some(witness) = &self.witness {
+            witness.validate(PermissionType::Witness)?;
+        }
+        if self.actives.is_empty() || self.actives.len() > MAX_ACTIVE_PERMISSIONS {
+            return Err(Error::InvalidArgument(format!(
+                "needs 1 to {} active permissions",
+                MAX_ACTIVE_PERMISSIONS
+            )));
+        }
+        for active in &self.actives {
+            active.validate(PermissionType::Active)?;
+        }
+
+        Ok(AccountPermissionUpdateContract {
+            owner_address: self.owner_address.into_inner(),
+            owner: Some(owner.to_permission(PermissionType::Owner, 0)),
+            witness: self
+                .witness
+                .as_ref()
+                .map(|w| w.to_permission(PermissionType::Witness, 1)),
+            actives: self
+                .actives
+                .iter()
+                .enumerate()
+                .map(|(i, a)| a.to_permission(PermissionType::Active, i as i32 + 2))
+                .collect(),
+        })
+    }
+
+    /// Changes made by the update to `account`, the current state of the owner account.
+    pub fn diff(&self, account: &Account) -> Result<Vec<PermissionChange>, Error> {
+        if !account.address.is_empty()
+            && Address::from_bytes(&account.address)? != self.owner_address
+        {
+            return Err(Error::InvalidArgument(format!(
+                "account is not {}",
+                self.owner_address
+            )));
+        }
+        match (account.is_witness, self.witness.is_some()) {
+            (false, true) => {
+                return Err(Error::InvalidArgument(
+                    "witness permission is only for witness accounts".to_string(),
+                ));
+            }
+            (true, false) => {
+                return Err(Error::InvalidArgument(
+                    "witness permission is missed".to_string(),
+                ));
+            }
+            _ => {}
+        }
+        let contract = self.build()?;
+
+        let mut current = vec![permission_by_id(account, 0)];
+        current.push(permission_by_id(account, 1));
+        current.extend(account.active_permission.iter().cloned().map(Some));
+        let mut new = vec![contract.owner, contract.witness];
+        new.extend(contract.actives.into_iter().map(Some));
+
+        let ids = current
+            .iter()
+            .chain(new.iter())
+            .flatten()
+            .map(|p| p.id)
+            .collect::<std::collections::BTreeSet<_>>();
+        let find = |list: &[Option<Permission>], id: i32| {
+            list.iter().flatten().find(|p| p.id == id).cloned()
+        };
+
+        let mut changes = Vec::new();
+        for id in ids {
+            match (find(&current, id), find(&new, id)) {
+                (None, Some(p)) => changes.push(PermissionChange::Added(p)),
+                (Some(p), None) => changes.push(PermissionChange::Removed(p)),
+                (Some(from), Some(to)) => diff_permission(&from, &to, &mut changes)?,
+                (None, None) => {}
+            }
+        }
+        Ok(changes)
+    }
+}
+
+impl GrpcClient {
+    /// Creates the unsigned `AccountPermissionUpdateContract` transaction of `update`.
+    pub async fn account_permission_update(
+        &mut self,
+        update: &PermissionUpdate,
+    ) -> Result<Response<TransactionExtention>, Error> {
+        let req = Request::new(update.build()?);
+        let mut resp = self.client().account_permission_update(req).await?;
+        let ext = resp.get_mut();
+        if let Some(e) = ext.result.as_ref().and_then(Error::from_return) {
+            return Err(e);
+        }
+        if let Some(raw) = ext.transaction.as_ref().and_then(|tx| tx.raw_data.as_ref()) {
+            ext.txid = Self::get_tx_hash(raw);
+        }
+        Ok(resp)
+    }
+}
+
+fn diff_permission(
+    from: &Permission,
+    to: &Permission,
+    changes: &mut Vec<PermissionChange>,
+) -> Result<(), Error> {
+    let id = to.id;
+    if from.permission_name != to.permission_name {
+        changes.push(PermissionChange::Name {
+            id,
+            from: from.permission_name.clone(),
+            to: to.permission_name.clone(),
+        });
+    }
+    if from.threshold != to.threshold {
+        changes.push(PermissionChange::Threshold {
+            id,
+            from: from.threshold,
+            to: to.threshold,
+        });
+    }
+    for key in &from.keys {
+        let address = Address::from_bytes(&key.address)?;
+        match to.keys.iter().find(|k| k.address == key.address) {
+            None => changes.push(PermissionChange::KeyRemoved {
+                id,
+                address,
+                weight: key.weight,
+            }),
+            Some(k) if k.weight != key.weight => changes.push(PermissionChange::KeyWeight {
+                id,
+                address,
+                from: key.weight,
+                to: k.weight,
+            }),
+            Some(_) => {}
+        }
+    }
+    for key in &to.keys {
+        if !from.keys.iter().any(|k| k.address == key.address) {
+            changes.push(PermissionChange::KeyAdded {
+                id,
+                address: Address::from_bytes(&key.address)?,
+                weight: key.weight,
+            });
+        }
+    }
+    let operations = |p: &Permission| {
+        OperationSet::from_bytes(&p.operations).unwrap_or_else(|_| OperationSet::empty())
+    };
+    let (from_ops, to_ops) = (operations(from), operations(to));
+    if from_ops != to_ops {
+        changes.push(PermissionChange::Operations {
+            id,
+            added: to_ops.iter().filter(|t| !from_ops.contains(*t)).collect(),
+            removed: from_ops.iter().filter(|t| !to_ops.contains(*t)).collect(),
+        });
+    }
+    Ok(())
+}
+
+/// One change of `PermissionUpdate::diff`, permissions are identified by id.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PermissionChange {
+    Added(Permission),
+    Removed(Permission),
+    Name {
+        id: i32,
+        from: String,
+        to: String,
+    },
+    Threshold {
+        id: i32,
+        from: i64,
+        to: i64,
+    },
+    KeyAdded {
+        id: i32,
+        address: Address,
+        weight: i64,
+    },
+    KeyRemoved {
+        id: i32,
+        address: Address,
+        weight: i64,
+    },
+    KeyWeight {
+        id: i32,
+        address: Address,
+        from: i64,
+        to: i64,
+    },
+    Operations {
+        id: i32,
+        added: OperationSet,
+        removed: OperationSet,
+    },
+}
+
+impl fmt::Display for PermissionChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added(p) => write!(
+                f,
+                "+ permission {} {:?}: threshold {}, {} keys",
+                p.id,
+                p.permission_name,
+                p.threshold,
+                p.keys.len()
+            ),
+            Self::Removed(p) => write!(f, "- permission {} {:?}", p.id, p.permission_name),
+            Self::Name { id, from, to } => {
+                write!(f, "~ permission {} name: {:?} -> {:?}", id, from, to)
+            }
+            Self::Threshold { id, from, to } => {
+                write!(f, "~ permission {} threshold: {} -> {}", id, from, to)
+            }
+            Self::KeyAdded {
+                id,
+                address,
+                weight,
+            } => write!(f, "+ permission {} key {} weight {}", id, address, weight),
+            Self::KeyRemoved {
+                id,
+                address,
+                weight,
+            } => write!(f, "- permission {} key {} weight {}", id, address, weight),
+            Self::KeyWeight {
+                id,
+                address,
+                from,
+                to,
+            } => write!(
+                f,
+                "~ permission {} key {} weight: {} -> {}",
+                id, address, from, to
+            ),
+            Self::Operations { id, added, removed } => write!(
+                f,
+                "~ permission {} operations: +[{}] -[{}]",
+                id, added, removed
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        api::{Account, permission::PermissionType, transaction::contract::ContractType},
+        multisig::{OperationSet, PermissionChange, PermissionSpec, PermissionUpdate},
+        signer::{LocalSigner, Signer},
+    };
+
+    #[test]
+    fn test_permission_update() {
+        let owner = LocalSigner::random().address();
+        let (a, b, c) = (
+            LocalSigner::random().address(),
+            LocalSigner::random().address(),
+            LocalSigner::random().address(),
+        );
+        let transfer = OperationSet::from_iter([ContractType::TransferContract]);
+        let update = PermissionUpdate::new(owner)
+            .owner(PermissionSpec::new("owner", 2).key(a, 1).key(b, 1))
+            .active(
+                PermissionSpec::new("payments", 1)
+                    .key(c, 1)
+                    .operations(transfer),
+            );
+
+        let contract = update.build().expect("build err");
+        let owner_permission = contract.owner.as_ref().unwrap();
+        assert_eq!(owner_permission.r#type, PermissionType::Owner as i32);
+        assert_eq!(owner_permission.keys.len(), 2);
+        assert!(owner_permission.operations.is_empty());
+        assert_eq!(contract.actives[0].id, 2);
+        assert_eq!(contract.actives[0].operations, Vec::<u8>::from(transfer));
+
+        let account = Account {
+            address: owner.into_inner(),
+            ..Default::default()
+        };
+        let changes = update.diff(&account).expect("diff err");
+        assert_eq!(changes[0].to_string(), "~ permission 0 threshold: 1 -> 2");
+        assert_eq!(
+            changes[1..3],
+            [
+                PermissionChange::KeyRemoved {
+                    id: 0,
+                    address: owner,
+                    weight: 1
+                },
+                PermissionChange::KeyAdded {
+                    id: 0,
+                    address: a,
+                    weight: 1
+                },
+            ]
+        );
+        assert_eq!(changes.len(), 5);
+        assert!(matches!(changes[4], PermissionChange::Added(ref p) if p.id == 2));
+
+        // a witness account must keep a witness permission
+        let witness_account = Account {
+            is_witness: true,
+            ..account.clone()
+        };
+        assert!(update.diff(&witness_account).is_err());
+        let changes = update
+            .clone()
+            .witness(PermissionSpec::new("witness", 1).key(a, 1))
+            .diff(&witness_account)
+            .expect("diff err");
+        assert!(changes.iter().any(|c| matches!(
+            c,
+            PermissionChange::KeyAdded { id: 1, address, .. } if *address == a
+        )));
+
+        // limits
+        let keys = |n: usize| {
+            (0..n).fold(PermissionSpec::new("owner", 1), |p, _| {
+                p.key(LocalSigner::random().address(), 1)
+            })
+        };
+        let active = PermissionSpec::new("active", 1)
+            .key(a, 1)
+            .operations(transfer);
+        let base = PermissionUpdate::new(owner).owner(keys(5));
+        assert!(base.clone().active(active.clone()).build().is_ok());
+        assert!(base.clone().build().is_err());
+        assert!(
+            PermissionUpdate::new(owner)
+                .owner(keys(6))
+                .active(active.clone())
+                .build()
+                .is_err()
+        );
+        assert!(
+            (0..9)
+                .fold(base.clone(), |u, _| u.active(active.clone()))
+                .build()
+                .is_err()
+        );
+        assert!(
+            base.clone()
+                .active(PermissionSpec::new("active", 3).key(a, 1).key(b, 1))
+                .build()
+                .is_err()
+        );
+        assert!(
+            base.clone()
+                .active(PermissionSpec::new("active", 1).key(a, 1))
+                .build()
+                .is_err()
+        );
+        assert!(
+            base.clone()
+                .active(active.clone().key(a, 1))
+                .build()
+                .is_err()
+        );
+        assert!(
+            base.active(active)
+                .witness(PermissionSpec::new("witness", 1).key(a, 1))
+                .diff(&account)
+                .is_err()
+        );
+    }
+}
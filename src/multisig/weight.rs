@@ -0,0 +1,181 @@
+use crate::{
+    Error,
+    api::{Account, Permission, Transaction, permission::PermissionType},
+    client::Address,
+    multisig::{OperationSet, permission_by_id, transaction_contract, verify_signers},
+};
+
+/// Signing progress of a transaction against the permission selected by its `permission_id`,
+/// evaluated the way the node validates multi-signatures.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignWeight {
+    pub permission: Permission,
+    /// Sum of the weights of `approved`.
+    pub weight: i64,
+    /// Distinct signers which are keys of the permission, in signing order.
+    pub approved: Vec<Address>,
+    /// Keys of the permission which have not signed.
+    pub missing: Vec<Address>,
+    /// Signers which are not keys of the permission.
+    pub unauthorized: Vec<Address>,
+    /// Signers which signed more than once.
+    pub duplicated: Vec<Address>,
+    /// The permission may authorize the contract type: owner permissions allow every type,
+    /// active permissions check `operations`, witness permissions allow none.
+    pub operation_allowed: bool,
+}
+
+impl SignWeight {
+    pub fn threshold(&self) -> i64 {
+        self.permission.threshold
+    }
+
+    /// Weight still needed to reach the threshold.
+    pub fn remaining(&self) -> i64 {
+        (self.threshold() - self.weight).max(0)
+    }
+
+    /// The node would accept the signatures: the threshold is reached and there are no
+    /// unauthorized or duplicated signatures.
+    pub fn is_enough(&self) -> bool {
+        self.operation_allowed
+            && self.remaining() == 0
+            && self.unauthorized.is_empty()
+            && self.duplicated.is_empty()
+    }
+}
+
+/// Evaluates the signatures of `tx` against `account`, the owner account of its contract,
+/// without asking a node(`get_transaction_sign_weight`).
+pub fn evaluate_sign_weight(tx: &Transaction, account: &Account) -> Result<SignWeight, Error> {
+    let checks = verify_signers(tx, account)?;
+    let contract = transaction_contract(tx)?;
+    let permission = permission_by_id(account, contract.permission_id).ok_or_else(|| {
+        Error::InvalidArgument(format!("permission {} not found", contract.permission_id))
+    })?;
+
+    let mut weight = 0;
+    let mut approved = Vec::new();
+    let mut unauthorized = Vec::new();
+    let mut duplicated = Vec::new();
+    for (i, check) in checks.iter().enumerate() {
+        if checks[..i].iter().any(|c| c.address == check.address) {
+            if !duplicated.contains(&check.address) {
+                duplicated.push(check.address);
+            }
+            continue;
+        }
+        match check.weight {
+            Some(w) => {
+                weight += w;
+                approved.push(check.address);
+            }
+            None => unauthorized.push(check.address),
+        }
+    }
+    let missing = permission
+        .keys
+        .iter()
+        .map(|k| Address::from_bytes(&k.address))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .filter(|a| !approved.contains(a))
+        .collect();
+
+    let operation_allowed = match PermissionType::try_from(permission.r#type) {
+        Ok(PermissionType::Owner) => true,
+        Ok(PermissionType::Active) => OperationSet::from_bytes(&permission.operations)
+            .is_ok_and(|ops| ops.contains_value(contract.r#type)),
+        _ => false,
+    };
+
+    Ok(SignWeight {
+        permission,
+        weight,
+        approved,
+        missing,
+        unauthorized,
+        duplicated,
+        operation_allowed,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        api::{
+            Account, Key, Permission, permission::PermissionType,
+            transaction::contract::ContractType,
+        },
+        multisig::{OperationSet, evaluate_sign_weight, test_transfer as transfer},
+        signer::{LocalSigner, Signer},
+    };
+
+    #[tokio::test]
+    async fn test_evaluate_sign_weight() {
+        let owner = LocalSigner::random();
+        let (a, b, c) = (
+            LocalSigner::random(),
+            LocalSigner::random(),
+            LocalSigner::random(),
+        );
+        let active = |id: i32, operations: OperationSet| Permission {
+            r#type: PermissionType::Active as i32,
+            id,
+            threshold: 3,
+            operations: operations.into(),
+            keys: [(&a, 2), (&b, 1), (&c, 1)]
+                .iter()
+                .map(|(s, weight)| Key {
+                    address: s.address().into_inner(),
+                    weight: *weight,
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let account = Account {
+            address: owner.address().into_inner(),
+            active_permission: vec![
+                active(2, OperationSet::from_iter([ContractType::TransferContract])),
+                active(
+                    3,
+                    OperationSet::from_iter([ContractType::TransferAssetContract]),
+                ),
+            ],
+            ..Default::default()
+        };
+
+        let tx = a.sign_transaction(transfer(&owner, 2)).await.unwrap();
+        let w = evaluate_sign_weight(&tx, &account).unwrap();
+        assert_eq!((w.weight, w.threshold(), w.remaining()), (2, 3, 1));
+        assert_eq!(w.missing, vec![b.address(), c.address()]);
+        assert!(w.operation_allowed && !w.is_enough());
+
+        let tx = b.sign_transaction(tx).await.unwrap();
+        let w = evaluate_sign_weight(&tx, &account).unwrap();
+        assert_eq!(w.approved, vec![a.address(), b.address()]);
+        assert!(w.is_enough());
+
+        let dup = a.sign_transaction(tx.clone()).await.unwrap();
+        let w = evaluate_sign_weight(&dup, &account).unwrap();
+        assert_eq!((w.weight, w.duplicated.clone()), (3, vec![a.address()]));
+        assert!(!w.is_enough());
+
+        let tx = owner.sign_transaction(tx).await.unwrap();
+        let w = evaluate_sign_weight(&tx, &account).unwrap();
+        assert_eq!(w.unauthorized, vec![owner.address()]);
+        assert!(!w.is_enough());
+
+        // the operations of permission 3 do not include TransferContract
+        let tx = transfer(&owner, 3);
+        let tx = a.sign_transaction(tx).await.unwrap();
+        let tx = b.sign_transaction(tx).await.unwrap();
+        let w = evaluate_sign_weight(&tx, &account).unwrap();
+        assert_eq!(w.remaining(), 0);
+        assert!(!w.operation_allowed && !w.is_enough());
+
+        // default owner permission
+        let tx = owner.sign_transaction(transfer(&owner, 0)).await.unwrap();
+        assert!(evaluate_sign_weight(&tx, &account).unwrap().is_enough());
+    }
+}
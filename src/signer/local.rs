@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+
+use crate::{
+    Error,
+    client::Address,
+    signer::Signer,
+    utils::crypto::{self, PrivateKey},
+};
+
+/// Signs with a secp256k1 secret key held in process memory.
+#[derive(Debug, Clone)]
+pub struct LocalSigner {
+    sk: PrivateKey,
+    address: Address,
+}
+
+impl LocalSigner {
+    pub fn new(sk: PrivateKey) -> Self {
+        let address = sk.address();
+        Self { sk, address }
+    }
+
+    pub fn from_hex(sk: &str) -> Result<Self, Error> {
+        Ok(Self::new(crypto::hex2sk(sk)?))
+    }
+
+    pub fn random() -> Self {
+        Self::new(PrivateKey::random())
+    }
+
+    pub fn private_key(&self) -> &PrivateKey {
+        &self.sk
+    }
+}
+
+#[async_trait]
+impl Signer for LocalSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_hash(&self, hash: &[u8]) -> Result<Vec<u8>, Error> {
+        crypto::sign_tx(hash.to_vec(), &self.sk)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        api::{Transaction, transaction},
+        client::GrpcClient,
+        signer::{LocalSigner, Signer},
+        utils::crypto,
+    };
+
+    const SK: &str = "399206ef884e86d46f103a76271a2ec33ebe28a65c600379b36e1d2748412a84";
+
+    #[tokio::test]
+    async fn test_sign_hash() {
+        let signer = LocalSigner::from_hex(SK).unwrap();
+        let txid = hex::decode("ef04ae1bba68efc0d43ff95762e202496308b00edd87b70aa2ddde06d0518a97")
+            .unwrap();
+        assert_eq!(
+            hex::encode(signer.sign_hash(&txid).await.unwrap()),
+            "209cc203bf512c8fd1d6c94821a8a128a36f821a72a7f76bd6750f8c9e7acca52a245338383797c5d239af5d70971ce57d0deb2e9f139ca3700263325d36ab6300"
+        );
+        assert!(signer.sign_hash(&txid[1..]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction() {
+        let signer = LocalSigner::from_hex(SK).unwrap();
+        let raw = transaction::Raw {
+            timestamp: 1_700_000_000_000,
+            ..Default::default()
+        };
+        let tx = signer
+            .sign_transaction(Transaction {
+                raw_data: Some(raw.clone()),
+                ..Default::default()
+            })
+            .await
+            .expect("sign transaction err");
+
+        assert_eq!(tx.signature.len(), 1);
+        assert_eq!(
+            tx.signature[0],
+            crypto::sign_tx(GrpcClient::get_tx_hash(&raw), signer.private_key()).unwrap()
+        );
+        assert!(
+            signer
+                .sign_transaction(Transaction::default())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sign_message() {
+        let signer = LocalSigner::random();
+        let sig = signer.sign_message(b"hello world").await.unwrap();
+        assert!(matches!(sig[64], 27 | 28));
+        assert_eq!(
+            crypto::verify_message(b"hello world", &sig).unwrap(),
+            signer.address()
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+
+use crate::{
+    Error,
+    api::Transaction,
+    client::Address,
+    client::GrpcClient,
+    utils::{crypto, typed_data::TypedData},
+};
+
+mod local;
+pub use local::LocalSigner;
+
+#[cfg(feature = "remote-signer")]
+pub mod remote;
+#[cfg(feature = "remote-signer")]
+pub use remote::RemoteSigner;
+
+/// Produces TRON signatures(65 bytes: r + s + recovery id) for one account.
+#[async_trait]
+pub trait Signer: Send + Sync {
+    fn address(&self) -> Address;
+
+    /// Signs a 32 bytes hash, e.g. a transaction id.
+    async fn sign_hash(&self, hash: &[u8]) -> Result<Vec<u8>, Error>;
+
+    /// Signs a message like TronWeb `signMessageV2`(TIP-191), the recovery id is 27/28.
+    /// Verify with `utils::crypto::verify_message`.
+    async fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let sig = self.sign_hash(&crypto::hash_message(message)).await?;
+        Ok(with_eth_recovery_id(sig))
+    }
+
+    /// Signs TIP-712 typed data like TronWeb `_signTypedData`, the recovery id is 27/28.
+    /// Verify with `utils::crypto::verify_typed_data`.
+    async fn sign_typed_data(&self, data: &TypedData) -> Result<Vec<u8>, Error> {
+        let sig = self.sign_hash(&data.hash()?).await?;
+        Ok(with_eth_recovery_id(sig))
+    }
+
+    /// Appends the signature of the transaction id to `tx.signature`.
+    async fn sign_transaction(&self, mut tx: Transaction) -> Result<Transaction, Error> {
+        let raw = tx
+            .raw_data
+            .as_ref()
+            .ok_or_else(|| Error::InvalidArgument("transaction raw data is empty".to_string()))?;
+        let sig = self.sign_hash(&GrpcClient::get_tx_hash(raw)).await?;
+        tx.signature.push(sig);
+        Ok(tx)
+    }
+}
+
+// wallets(TronLink, TronWeb) encode the recovery id of off-chain signatures as 27/28
+fn with_eth_recovery_id(mut sig: Vec<u8>) -> Vec<u8> {
+    if let Some(v) = sig.get_mut(64)
+        && *v < 27
+    {
+        *v += 27;
+    }
+    sig
+}
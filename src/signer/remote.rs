@@ -0,0 +1,305 @@
+//! HTTP/JSON remote signing protocol.
+//!
+//! - `GET /address` -> `AddressResponse`
+//! - `POST /sign` with `SignRequest` -> `SignResponse`
+//!
+//! Errors are returned with a non-2xx status and an `ErrorResponse` body.
+//! Bytes are hex encoded, addresses are base58.
+
+use std::convert::Infallible;
+
+use async_trait::async_trait;
+use http_body_util::{BodyExt, Full};
+use hyper::{
+    Method, Request, Response, StatusCode,
+    body::{Bytes, Incoming},
+    header::CONTENT_TYPE,
+    service::service_fn,
+};
+use hyper_util::{
+    client::legacy::{Client, connect::HttpConnector},
+    rt::{TokioExecutor, TokioIo},
+};
+use prost::Message;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use sha2::{Digest, Sha256};
+use tokio::net::TcpListener;
+
+use crate::{
+    Error,
+    api::Transaction,
+    client::{Address, GrpcClient},
+    signer::{LocalSigner, Signer},
+    utils::crypto,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressResponse {
+    pub address: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignRequest {
+    /// expected signer address
+    pub address: String,
+    pub txid: String,
+    /// protobuf encoded `transaction::Raw`, absent when signing a bare hash
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_data: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignResponse {
+    /// 65 bytes: r + s + recovery id
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// Delegates signing to an external service. Every returned signature is checked by
+/// recovering its signer address before use.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    endpoint: String,
+    address: Address,
+    client: Client<HttpConnector, Full<Bytes>>,
+}
+
+impl RemoteSigner {
+    /// `endpoint` is the service base url, e.g. `http://127.0.0.1:8090`.
+    pub fn new(endpoint: &str, address: Address) -> Self {
+        Self {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            address,
+            client: Client::builder(TokioExecutor::new()).build_http(),
+        }
+    }
+
+    /// Creates a signer for the address served by the service.
+    pub async fn connect(endpoint: &str) -> Result<Self, Error> {
+        let mut signer = Self::new(endpoint, Address::new([0; 20]));
+        let resp: AddressResponse = signer.request(Method::GET, "/address", None).await?;
+        signer.address = resp.address.parse()?;
+        Ok(signer)
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Vec<u8>>,
+    ) -> Result<T, Error> {
+        let req = Request::builder()
+            .method(method)
+            .uri(format!("{}{}", self.endpoint, path))
+            .header(CONTENT_TYPE, "application/json")
+            .body(Full::new(Bytes::from(body.unwrap_or_default())))
+            .map_err(|e| Error::Signer(format!("build request err: {}", e)))?;
+        let resp = self
+            .client
+            .request(req)
+            .await
+            .map_err(|e| Error::Signer(format!("request err: {}", e)))?;
+        let status = resp.status();
+        let body = resp
+            .into_body()
+            .collect()
+            .await
+            .map_err(|e| Error::Signer(format!("read response err: {}", e)))?
+            .to_bytes();
+        if !status.is_success() {
+            let msg = serde_json::from_slice::<ErrorResponse>(&body)
+                .map(|e| e.error)
+                .unwrap_or_else(|_| String::from_utf8_lossy(&body).into_owned());
+            return Err(Error::Signer(format!("{}: {}", status, msg)));
+        }
+        serde_json::from_slice(&body)
+            .map_err(|e| Error::Signer(format!("decode response err: {}", e)))
+    }
+
+    async fn sign(&self, txid: &[u8], raw_data: Option<Vec<u8>>) -> Result<Vec<u8>, Error> {
+        let req = SignRequest {
+            address: self.address.to_bs58(),
+            txid: hex::encode(txid),
+            raw_data: raw_data.map(hex::encode),
+        };
+        let body = serde_json::to_vec(&req)
+            .map_err(|e| Error::Signer(format!("encode request err: {}", e)))?;
+        let resp: SignResponse = self.request(Method::POST, "/sign", Some(body)).await?;
+        let sig = hex::decode(&resp.signature)
+            .map_err(|e| Error::Signer(format!("decode signature err: {}", e)))?;
+
+        let signer = crypto::recover_address(txid, &sig)?;
+        if signer != self.address {
+            return Err(Error::Signer(format!(
+                "signature is signed by {}, expect {}",
+                signer, self.address
+            )));
+        }
+        Ok(sig)
+    }
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_hash(&self, hash: &[u8]) -> Result<Vec<u8>, Error> {
+        self.sign(hash, None).await
+    }
+
+    async fn sign_transaction(&self, mut tx: Transaction) -> Result<Transaction, Error> {
+        let raw = tx
+            .raw_data
+            .as_ref()
+            .ok_or_else(|| Error::InvalidArgument("transaction raw data is empty".to_string()))?;
+        let sig = self
+            .sign(&GrpcClient::get_tx_hash(raw), Some(raw.encode_to_vec()))
+            .await?;
+        tx.signature.push(sig);
+        Ok(tx)
+    }
+}
+
+/// Reference signing service backed by a `LocalSigner`, serving until the listener fails.
+pub async fn serve(listener: TcpListener, signer: LocalSigner) -> Result<(), Error> {
+    loop {
+        let (stream, _) = listener
+            .accept()
+            .await
+            .map_err(|e| Error::Signer(format!("accept err: {}", e)))?;
+        let signer = signer.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |req| handle(req, signer.clone()));
+            // a broken connection only affects its own requests
+            let _ = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await;
+        });
+    }
+}
+
+async fn handle(
+    req: Request<Incoming>,
+    signer: LocalSigner,
+) -> Result<Response<Full<Bytes>>, Infallible> {
+    let resp = match (req.method(), req.uri().path()) {
+        (&Method::GET, "/address") => json_response(
+            StatusCode::OK,
+            &AddressResponse {
+                address: signer.address().to_bs58(),
+            },
+        ),
+        (&Method::POST, "/sign") => match req.into_body().collect().await {
+            Ok(body) => match handle_sign(&body.to_bytes(), &signer).await {
+                Ok(resp) => json_response(StatusCode::OK, &resp),
+                Err(e) => error_response(StatusCode::BAD_REQUEST, e),
+            },
+            Err(e) => error_response(StatusCode::BAD_REQUEST, e),
+        },
+        _ => error_response(StatusCode::NOT_FOUND, "not found"),
+    };
+    Ok(resp)
+}
+
+async fn handle_sign(body: &[u8], signer: &LocalSigner) -> Result<SignResponse, String> {
+    let req: SignRequest = serde_json::from_slice(body).map_err(|e| e.to_string())?;
+    let address: Address = req.address.parse().map_err(|e: Error| e.to_string())?;
+    if address != signer.address() {
+        return Err(format!("unknown signer address: {}", address));
+    }
+    let txid = hex::decode(&req.txid).map_err(|e| format!("invalid txid: {}", e))?;
+    if let Some(raw_data) = req.raw_data {
+        let raw_data = hex::decode(raw_data).map_err(|e| format!("invalid raw data: {}", e))?;
+        if Sha256::digest(&raw_data).as_slice() != txid {
+            return Err("txid mismatch raw data".to_string());
+        }
+    }
+    let sig = signer.sign_hash(&txid).await.map_err(|e| e.to_string())?;
+    Ok(SignResponse {
+        signature: hex::encode(sig),
+    })
+}
+
+fn json_response<T: Serialize>(status: StatusCode, body: &T) -> Response<Full<Bytes>> {
+    let mut resp = Response::new(Full::new(Bytes::from(
+        serde_json::to_vec(body).expect("serialize json response"),
+    )));
+    *resp.status_mut() = status;
+    resp.headers_mut().insert(
+        CONTENT_TYPE,
+        "application/json".parse().expect("content type"),
+    );
+    resp
+}
+
+fn error_response(status: StatusCode, e: impl ToString) -> Response<Full<Bytes>> {
+    json_response(
+        status,
+        &ErrorResponse {
+            error: e.to_string(),
+        },
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use tokio::net::TcpListener;
+
+    use crate::{
+        Error,
+        api::{Transaction, transaction},
+        signer::{LocalSigner, RemoteSigner, Signer, remote::serve},
+    };
+
+    async fn start_server(signer: LocalSigner) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(serve(listener, signer));
+        endpoint
+    }
+
+    #[tokio::test]
+    async fn test_remote_sign() {
+        let local = LocalSigner::random();
+        let endpoint = start_server(local.clone()).await;
+
+        let remote = RemoteSigner::connect(&endpoint)
+            .await
+            .expect("connect remote signer err");
+        assert_eq!(remote.address(), local.address());
+
+        let tx = Transaction {
+            raw_data: Some(transaction::Raw {
+                timestamp: 1_700_000_000_000,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            remote.sign_transaction(tx.clone()).await.unwrap(),
+            local.sign_transaction(tx).await.unwrap()
+        );
+
+        let hash = [7u8; 32];
+        assert_eq!(
+            remote.sign_hash(&hash).await.unwrap(),
+            local.sign_hash(&hash).await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remote_sign_wrong_address() {
+        let endpoint = start_server(LocalSigner::random()).await;
+        let remote = RemoteSigner::new(&endpoint, LocalSigner::random().address());
+        assert!(matches!(
+            remote.sign_hash(&[7u8; 32]).await,
+            Err(Error::Signer(_))
+        ));
+    }
+}
@@ -0,0 +1,524 @@
+//! Sign-In-With-TRON, EIP-4361 style login messages signed with TIP-191(`signMessageV2`).
+//!
+//! ```text
+//! example.com wants you to sign in with your TRON account:
+//! TUEZSdKsoDHQMeZwihtdoBiN46zxhGWYdH
+//!
+//! Sign in to Example.
+//!
+//! URI: https://example.com/login
+//! Version: 1
+//! Chain ID: 728126428
+//! Nonce: 8vPxYl2kQw0ZtR7c
+//! Issued At: 2025-01-01T00:00:00Z
+//! Expiration Time: 2025-01-01T00:05:00Z
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt,
+    str::FromStr,
+    sync::{Mutex, MutexGuard},
+    time::Duration,
+};
+
+use secp256k1::rand::{self, Rng, distr::Alphanumeric};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+
+use crate::{Error, client::Address, utils::crypto};
+
+const HEADER_SUFFIX: &str = " wants you to sign in with your TRON account:";
+const VERSION: &str = "1";
+
+/// A Sign-In-With-TRON message, `Display` and `FromStr` convert the text to sign.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiwtMessage {
+    /// The host(and optional port) requesting the sign in.
+    pub domain: String,
+    pub address: Address,
+    /// Human readable statement, must not contain `\n`.
+    pub statement: Option<String>,
+    pub uri: String,
+    pub version: String,
+    /// e.g. `728126428`(`0x2b6653dc`) for the mainnet.
+    pub chain_id: u64,
+    /// At least 8 alphanumeric characters.
+    pub nonce: String,
+    pub issued_at: OffsetDateTime,
+    pub expiration_time: Option<OffsetDateTime>,
+    pub not_before: Option<OffsetDateTime>,
+    pub request_id: Option<String>,
+    pub resources: Vec<String>,
+}
+
+impl SiwtMessage {
+    /// A message with a random nonce, issued now.
+    pub fn new(domain: &str, address: Address, uri: &str, chain_id: u64) -> Self {
+        Self {
+            domain: domain.to_string(),
+            address,
+            statement: None,
+            uri: uri.to_string(),
+            version: VERSION.to_string(),
+            chain_id,
+            nonce: generate_nonce(),
+            issued_at: now(),
+            expiration_time: None,
+            not_before: None,
+            request_id: None,
+            resources: Vec::new(),
+        }
+    }
+
+    /// Checks everything except the signature: domain, nonce and the validity window at `now`.
+    pub fn validate(&self, domain: &str, nonce: &str, now: OffsetDateTime) -> Result<(), Error> {
+        if self.domain != domain {
+            return Err(Error::Auth(format!("domain mismatch: {}", self.domain)));
+        }
+        if self.nonce != nonce {
+            return Err(Error::Auth(format!("nonce mismatch: {}", self.nonce)));
+        }
+        if self.expiration_time.is_some_and(|t| now >= t) {
+            return Err(Error::Auth("message expired".to_string()));
+        }
+        if self.not_before.is_some_and(|t| now < t) {
+            return Err(Error::Auth("message is not valid yet".to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SiwtMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}{}", self.domain, HEADER_SUFFIX)?;
+        writeln!(f, "{}", self.address)?;
+        writeln!(f)?;
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{}", statement)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "URI: {}", self.uri)?;
+        writeln!(f, "Version: {}", self.version)?;
+        writeln!(f, "Chain ID: {}", self.chain_id)?;
+        writeln!(f, "Nonce: {}", self.nonce)?;
+        write!(f, "Issued At: {}", format_time(self.issued_at)?)?;
+        if let Some(t) = self.expiration_time {
+            write!(f, "\nExpiration Time: {}", format_time(t)?)?;
+        }
+        if let Some(t) = self.not_before {
+            write!(f, "\nNot Before: {}", format_time(t)?)?;
+        }
+        if let Some(id) = &self.request_id {
+            write!(f, "\nRequest ID: {}", id)?;
+        }
+        if !self.resources.is_empty() {
+            write!(f, "\nResources:")?;
+            for r in &self.resources {
+                write!(f, "\n- {}", r)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SiwtMessage {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.split('\n').peekable();
+        let mut next = |what: &str| {
+            lines
+                .next()
+                .ok_or_else(|| Error::Auth(format!("invalid message: missing {}", what)))
+        };
+
+        let domain = next("header")?
+            .strip_suffix(HEADER_SUFFIX)
+            .filter(|d| !d.is_empty())
+            .ok_or_else(|| Error::Auth("invalid message header".to_string()))?
+            .to_string();
+        let address: Address = next("address")?
+            .parse()
+            .map_err(|e| Error::Auth(format!("invalid message address: {}", e)))?;
+        expect_empty(next("empty line")?)?;
+        let statement = match next("statement")? {
+            "" => None,
+            statement => {
+                expect_empty(next("empty line")?)?;
+                Some(statement.to_string())
+            }
+        };
+
+        let uri = tagged(next("URI")?, "URI")?.to_string();
+        let version = tagged(next("Version")?, "Version")?.to_string();
+        if version != VERSION {
+            return Err(Error::Auth(format!("unsupported version: {}", version)));
+        }
+        let chain_id = tagged(next("Chain ID")?, "Chain ID")?
+            .parse()
+            .map_err(|e| Error::Auth(format!("invalid chain id: {}", e)))?;
+        let nonce = tagged(next("Nonce")?, "Nonce")?.to_string();
+        if nonce.len() < 8 || !nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(Error::Auth(format!("invalid nonce: {}", nonce)));
+        }
+        let issued_at = parse_time(tagged(next("Issued At")?, "Issued At")?)?;
+
+        let mut optional = |tag: &str| {
+            let v = lines.peek().and_then(|l| l.strip_prefix(tag))?;
+            lines.next();
+            Some(v)
+        };
+        let expiration_time = optional("Expiration Time: ").map(parse_time).transpose()?;
+        let not_before = optional("Not Before: ").map(parse_time).transpose()?;
+        let request_id = optional("Request ID: ").map(str::to_string);
+        let mut resources = Vec::new();
+        match optional("Resources:") {
+            Some("") => {
+                while let Some(r) = lines.peek().and_then(|l| l.strip_prefix("- ")) {
+                    resources.push(r.to_string());
+                    lines.next();
+                }
+            }
+            Some(r) => return Err(Error::Auth(format!("invalid message resources: {}", r))),
+            None => {}
+        }
+        if let Some(line) = lines.next() {
+            return Err(Error::Auth(format!("invalid message line: {}", line)));
+        }
+
+        Ok(Self {
+            domain,
+            address,
+            statement,
+            uri,
+            version,
+            chain_id,
+            nonce,
+            issued_at,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+        })
+    }
+}
+
+/// Parses the signed `message` and checks the signature, domain, nonce and validity window.
+/// `signature` is the `signMessageV2` signature of the exact `message` text.
+pub fn verify(
+    message: &str,
+    signature: &[u8],
+    domain: &str,
+    nonce: &str,
+) -> Result<SiwtMessage, Error> {
+    let msg: SiwtMessage = message.parse()?;
+    msg.validate(domain, nonce, now())?;
+    let signer = crypto::verify_message(message.as_bytes(), signature)
+        .map_err(|e| Error::Auth(format!("invalid signature: {}", e)))?;
+    if signer != msg.address {
+        return Err(Error::Auth(format!(
+            "message is signed by {}, expect {}",
+            signer, msg.address
+        )));
+    }
+    Ok(msg)
+}
+
+/// Issues login challenges and verifies their responses, each nonce is accepted only once.
+/// Outstanding nonces are kept in memory.
+#[derive(Debug)]
+pub struct SiwtAuthenticator {
+    domain: String,
+    uri: String,
+    chain_id: u64,
+    statement: Option<String>,
+    ttl: Duration,
+    nonces: Mutex<HashMap<String, Challenge>>,
+}
+
+// An outstanding challenge, by nonce.
+#[derive(Debug, Clone, Copy)]
+struct Challenge {
+    address: Address,
+    expiration: OffsetDateTime,
+}
+
+impl SiwtAuthenticator {
+    pub fn new(domain: &str, uri: &str, chain_id: u64) -> Self {
+        Self {
+            domain: domain.to_string(),
+            uri: uri.to_string(),
+            chain_id,
+            statement: None,
+            ttl: Duration::from_secs(300),
+            nonces: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn statement(mut self, statement: &str) -> Self {
+        self.statement = Some(statement.to_string());
+        self
+    }
+
+    /// How long a challenge is valid, default 5 minutes.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// A new challenge for `address`, send `to_string()` of it to the wallet to sign.
+    pub fn challenge(&self, address: Address) -> SiwtMessage {
+        let mut msg = SiwtMessage::new(&self.domain, address, &self.uri, self.chain_id);
+        msg.statement = self.statement.clone();
+        let expiration = msg.issued_at + self.ttl;
+        msg.expiration_time = Some(expiration);
+
+        let mut nonces = self.nonces();
+        nonces.retain(|_, c| c.expiration > msg.issued_at);
+        nonces.insert(
+            msg.nonce.clone(),
+            Challenge {
+                address,
+                expiration,
+            },
+        );
+        msg
+    }
+
+    /// Verifies a signed challenge issued by this authenticator: the message must keep the
+    /// issued address, URI, chain id and expiration time, and the challenge must not have
+    /// expired. The nonce is consumed once the verification succeeds.
+    pub fn verify(&self, message: &str, signature: &[u8]) -> Result<SiwtMessage, Error> {
+        let msg: SiwtMessage = message.parse()?;
+        let unknown_nonce = || Error::Auth(format!("unknown nonce: {}", msg.nonce));
+        let challenge = {
+            let mut nonces = self.nonces();
+            let now = now();
+            nonces.retain(|_, c| c.expiration > now);
+            *nonces.get(&msg.nonce).ok_or_else(unknown_nonce)?
+        };
+        if msg.address != challenge.address {
+            return Err(Error::Auth(format!(
+                "nonce is issued for {}, not {}",
+                challenge.address, msg.address
+            )));
+        }
+        if msg.expiration_time != Some(challenge.expiration) {
+            return Err(Error::Auth("expiration time mismatch".to_string()));
+        }
+        if msg.uri != self.uri {
+            return Err(Error::Auth(format!("uri mismatch: {}", msg.uri)));
+        }
+        if msg.chain_id != self.chain_id {
+            return Err(Error::Auth(format!("chain id mismatch: {}", msg.chain_id)));
+        }
+        let msg = verify(message, signature, &self.domain, &msg.nonce)?;
+        // a concurrent verification of the same challenge may have won
+        match self.nonces().remove(&msg.nonce) {
+            Some(_) => Ok(msg),
+            None => Err(unknown_nonce()),
+        }
+    }
+
+    fn nonces(&self) -> MutexGuard<'_, HashMap<String, Challenge>> {
+        self.nonces.lock().expect("nonce lock poisoned")
+    }
+}
+
+/// 16 random alphanumeric characters.
+pub fn generate_nonce() -> String {
+    rand::rng()
+        .sample_iter(Alphanumeric)
+        .take(16)
+        .map(char::from)
+        .collect()
+}
+
+fn now() -> OffsetDateTime {
+    OffsetDateTime::now_utc()
+        .replace_nanosecond(0)
+        .expect("zero nanosecond is valid")
+}
+
+fn format_time(t: OffsetDateTime) -> Result<String, fmt::Error> {
+    t.format(&Rfc3339).map_err(|_| fmt::Error)
+}
+
+fn parse_time(s: &str) -> Result<OffsetDateTime, Error> {
+    OffsetDateTime::parse(s, &Rfc3339)
+        .map_err(|e| Error::Auth(format!("invalid message time {}: {}", s, e)))
+}
+
+fn tagged<'a>(line: &'a str, tag: &str) -> Result<&'a str, Error> {
+    line.strip_prefix(tag)
+        .and_then(|l| l.strip_prefix(": "))
+        .ok_or_else(|| Error::Auth(format!("invalid message: expect {}", tag)))
+}
+
+fn expect_empty(line: &str) -> Result<(), Error> {
+    if !line.is_empty() {
+        return Err(Error::Auth(format!("invalid message line: {}", line)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+
+    use super::{SiwtAuthenticator, SiwtMessage};
+    use crate::{
+        Error,
+        signer::{LocalSigner, Signer},
+    };
+
+    const MESSAGE: &str = "example.com wants you to sign in with your TRON account:
+TUEZSdKsoDHQMeZwihtdoBiN46zxhGWYdH
+
+Sign in to Example.
+
+URI: https://example.com/login
+Version: 1
+Chain ID: 728126428
+Nonce: 8vPxYl2kQw0ZtR7c
+Issued At: 2025-01-01T00:00:00Z
+Expiration Time: 2025-01-01T00:05:00Z
+Request ID: 42
+Resources:
+- https://example.com/terms
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq";
+
+    fn time(s: &str) -> OffsetDateTime {
+        OffsetDateTime::parse(s, &Rfc3339).unwrap()
+    }
+
+    #[test]
+    fn test_parse_message() {
+        let msg: SiwtMessage = MESSAGE.parse().expect("parse message err");
+        assert_eq!(msg.domain, "example.com");
+        assert_eq!(msg.address.to_bs58(), "TUEZSdKsoDHQMeZwihtdoBiN46zxhGWYdH");
+        assert_eq!(msg.statement.as_deref(), Some("Sign in to Example."));
+        assert_eq!(msg.chain_id, 728126428);
+        assert_eq!(msg.issued_at, time("2025-01-01T00:00:00Z"));
+        assert_eq!(msg.expiration_time, Some(time("2025-01-01T00:05:00Z")));
+        assert_eq!(msg.not_before, None);
+        assert_eq!(msg.request_id.as_deref(), Some("42"));
+        assert_eq!(msg.resources.len(), 2);
+        assert_eq!(msg.to_string(), MESSAGE);
+
+        let mut msg = msg;
+        msg.statement = None;
+        msg.resources.clear();
+        let text = msg.to_string();
+        assert!(text.contains("TUEZSdKsoDHQMeZwihtdoBiN46zxhGWYdH\n\n\nURI: "));
+        assert_eq!(text.parse::<SiwtMessage>().unwrap(), msg);
+
+        assert!(
+            MESSAGE
+                .replace("Version: 1", "Version: 2")
+                .parse::<SiwtMessage>()
+                .is_err()
+        );
+        assert!(
+            MESSAGE
+                .replace("8vPxYl2kQw0ZtR7c", "abc")
+                .parse::<SiwtMessage>()
+                .is_err()
+        );
+        assert!(
+            format!("{}\nextra", MESSAGE)
+                .parse::<SiwtMessage>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let msg: SiwtMessage = MESSAGE.parse().unwrap();
+        let now = time("2025-01-01T00:01:00Z");
+        msg.validate("example.com", "8vPxYl2kQw0ZtR7c", now)
+            .unwrap();
+        assert!(msg.validate("evil.com", "8vPxYl2kQw0ZtR7c", now).is_err());
+        assert!(
+            msg.validate("example.com", "0000000000000000", now)
+                .is_err()
+        );
+        assert!(
+            msg.validate(
+                "example.com",
+                "8vPxYl2kQw0ZtR7c",
+                time("2025-01-01T00:05:00Z")
+            )
+            .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_authenticator() {
+        let auth = SiwtAuthenticator::new("example.com", "https://example.com/login", 728126428)
+            .statement("Sign in to Example.")
+            .ttl(Duration::from_secs(60));
+        let signer = LocalSigner::random();
+
+        let text = auth.challenge(signer.address()).to_string();
+        let sig = signer.sign_message(text.as_bytes()).await.unwrap();
+        let msg = auth.verify(&text, &sig).expect("verify err");
+        assert_eq!(msg.address, signer.address());
+        // replay
+        assert!(matches!(auth.verify(&text, &sig), Err(Error::Auth(_))));
+
+        // signed by another account
+        let text = auth.challenge(signer.address()).to_string();
+        let sig = LocalSigner::random()
+            .sign_message(text.as_bytes())
+            .await
+            .unwrap();
+        assert!(matches!(auth.verify(&text, &sig), Err(Error::Auth(_))));
+
+        // not issued by the authenticator
+        let mut msg = auth.challenge(signer.address());
+        msg.nonce = super::generate_nonce();
+        let text = msg.to_string();
+        let sig = signer.sign_message(text.as_bytes()).await.unwrap();
+        assert!(matches!(auth.verify(&text, &sig), Err(Error::Auth(_))));
+
+        // changed address, uri or expiration time
+        let other = LocalSigner::random();
+        let mut msg = auth.challenge(signer.address());
+        msg.address = other.address();
+        let text = msg.to_string();
+        let sig = other.sign_message(text.as_bytes()).await.unwrap();
+        assert!(matches!(auth.verify(&text, &sig), Err(Error::Auth(_))));
+        let mut msg = auth.challenge(signer.address());
+        msg.uri = "https://evil.com/login".to_string();
+        let text = msg.to_string();
+        let sig = signer.sign_message(text.as_bytes()).await.unwrap();
+        assert!(matches!(auth.verify(&text, &sig), Err(Error::Auth(_))));
+        let mut msg = auth.challenge(signer.address());
+        msg.expiration_time = msg.expiration_time.map(|t| t + Duration::from_secs(3600));
+        let text = msg.to_string();
+        let sig = signer.sign_message(text.as_bytes()).await.unwrap();
+        assert!(matches!(auth.verify(&text, &sig), Err(Error::Auth(_))));
+
+        // the expiration line removed
+        let mut msg = auth.challenge(signer.address());
+        msg.expiration_time = None;
+        let text = msg.to_string();
+        let sig = signer.sign_message(text.as_bytes()).await.unwrap();
+        assert!(matches!(auth.verify(&text, &sig), Err(Error::Auth(_))));
+
+        // expired, also without the expiration line
+        let auth = SiwtAuthenticator::new("example.com", "https://example.com/login", 728126428)
+            .ttl(Duration::ZERO);
+        let text = auth.challenge(signer.address()).to_string();
+        let sig = signer.sign_message(text.as_bytes()).await.unwrap();
+        assert!(matches!(auth.verify(&text, &sig), Err(Error::Auth(_))));
+        let mut msg = auth.challenge(signer.address());
+        msg.expiration_time = None;
+        let text = msg.to_string();
+        let sig = signer.sign_message(text.as_bytes()).await.unwrap();
+        assert!(matches!(auth.verify(&text, &sig), Err(Error::Auth(_))));
+    }
+}
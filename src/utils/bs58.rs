@@ -1,34 +1,36 @@
 use sha2::{Digest, Sha256};
 
+use crate::Error;
+
 pub fn encode<I: AsRef<[u8]>>(data: I) -> String {
     bs58::encode(data)
         .with_alphabet(bs58::Alphabet::BITCOIN)
         .into_string()
 }
 
-pub fn decode<I: AsRef<[u8]>>(data: I) -> Result<Vec<u8>, String> {
+pub fn decode<I: AsRef<[u8]>>(data: I) -> Result<Vec<u8>, Error> {
     bs58::decode(data)
         .with_alphabet(bs58::Alphabet::BITCOIN)
         .into_vec()
-        .map_err(|e| e.to_string())
+        .map_err(|e| Error::Address(e.to_string()))
 }
 
 // base58check: https://developers.tron.network/docs/account#account-address-formats
 // tron base58 address = `0x41` + <20 bytes of ethereum address> + <4 bytes of checksum>
-pub fn decode_address(address: &str) -> Result<Vec<u8>, String> {
+pub fn decode_address(address: &str) -> Result<Vec<u8>, Error> {
     let data = decode(address)?;
     if data.len() != 25 {
-        return Err(format!(
+        return Err(Error::Address(format!(
             "invalid address length: get:{}, expect: 25",
             data.len()
-        ));
+        )));
     }
     // check prefix
     if data[0] != 0x41 {
-        return Err(format!(
+        return Err(Error::Address(format!(
             "invalid address prefix: get:{}, expect: 0x41",
             data[0]
-        ));
+        )));
     }
 
     let (dec_address, checksum) = data.split_at(21);
@@ -39,10 +41,10 @@ pub fn decode_address(address: &str) -> Result<Vec<u8>, String> {
 
     for (i, v) in checksum.iter().enumerate() {
         if hh[i] != *v {
-            return Err(format!(
+            return Err(Error::Address(format!(
                 "invalid address checksum(index: {}): get:{}, expect:{}",
                 i, hh[i], *v
-            ));
+            )));
         }
     }
 
@@ -0,0 +1,137 @@
+//! Contract addresses known before deployment, derived the way java-tron does.
+
+use prost::Message;
+use sha3::{Digest, Keccak256};
+
+use crate::{
+    Error,
+    api::{CreateSmartContract, Transaction, transaction::contract::ContractType},
+    client::{Address, GrpcClient},
+};
+
+/// Address of a contract deployed by a `CreateSmartContract` transaction:
+/// `keccak256(txid ++ owner_address)[12..]`.
+pub fn create_address(owner: &Address, txid: &[u8]) -> Address {
+    let mut hasher = Keccak256::new();
+    hasher.update(txid);
+    hasher.update(owner.as_bytes());
+    from_hash(hasher.finalize().into())
+}
+
+/// Like `create_address`, reading the owner and the txid from the deploy transaction. The
+/// txid depends on the whole raw data, so derive it from the final transaction.
+pub fn create_address_from_transaction(tx: &Transaction) -> Result<Address, Error> {
+    let raw = tx
+        .raw_data
+        .as_ref()
+        .ok_or_else(|| Error::InvalidArgument("transaction raw data is empty".to_string()))?;
+    let contract = raw
+        .contract
+        .first()
+        .filter(|c| c.r#type == ContractType::CreateSmartContract as i32)
+        .ok_or_else(|| {
+            Error::InvalidArgument("not a CreateSmartContract transaction".to_string())
+        })?;
+    let create = contract
+        .parameter
+        .as_ref()
+        .map(|any| CreateSmartContract::decode(any.value.as_slice()))
+        .transpose()
+        .map_err(|e| Error::InvalidArgument(format!("decode contract parameter err: {}", e)))?
+        .unwrap_or_default();
+    let owner = Address::from_bytes(&create.owner_address)?;
+    Ok(create_address(&owner, &GrpcClient::get_tx_hash(raw)))
+}
+
+/// Address of a contract deployed by the `CREATE` opcode: `keccak256(txid ++ nonce)[12..]`,
+/// with the txid of the root transaction and the big-endian nonce of the internal
+/// transaction within it.
+pub fn create_address_with_nonce(txid: &[u8], nonce: u64) -> Address {
+    let mut hasher = Keccak256::new();
+    hasher.update(txid);
+    hasher.update(nonce.to_be_bytes());
+    from_hash(hasher.finalize().into())
+}
+
+/// Address of a contract deployed by the `CREATE2` opcode:
+/// `keccak256(0x41 ++ deployer ++ salt ++ keccak256(init_code))[12..]`, TRON uses the
+/// address prefix where ethereum uses `0xff`.
+pub fn create2_address(deployer: &Address, salt: &[u8; 32], init_code_hash: &[u8; 32]) -> Address {
+    let mut hasher = Keccak256::new();
+    hasher.update(deployer.as_bytes());
+    hasher.update(salt);
+    hasher.update(init_code_hash);
+    from_hash(hasher.finalize().into())
+}
+
+/// The `init_code_hash` of `create2_address`: creation bytecode followed by the ABI-encoded
+/// constructor arguments.
+pub fn init_code_hash(init_code: &[u8]) -> [u8; 32] {
+    Keccak256::digest(init_code).into()
+}
+
+fn from_hash(hash: [u8; 32]) -> Address {
+    Address::new(hash[12..].try_into().expect("keccak256 digest is 32 bytes"))
+}
+
+#[cfg(test)]
+mod test {
+    use prost::Message;
+
+    use super::{
+        create_address, create_address_from_transaction, create_address_with_nonce,
+        create2_address, init_code_hash,
+    };
+    use crate::{
+        api::{CreateSmartContract, Transaction, transaction},
+        client::{Address, GrpcClient},
+    };
+
+    #[test]
+    fn test_contract_address() {
+        let owner: Address = "TUEZSdKsoDHQMeZwihtdoBiN46zxhGWYdH".parse().unwrap();
+        let txid = [0x11; 32];
+        assert_eq!(
+            create_address(&owner, &txid).to_hex(),
+            "415e91ea2044a43f97cd85d0626904c2692666203a"
+        );
+        assert_eq!(
+            create_address_with_nonce(&txid, 1).to_hex(),
+            "41591af7e274e40e63f6c7db67f443efa2f6765fc2"
+        );
+
+        let code = hex::decode("6080604052").unwrap();
+        assert_eq!(
+            create2_address(&owner, &[0x22; 32], &init_code_hash(&code)).to_hex(),
+            "4182ad9fe9d192284ed19b69844c4c742c425f8c82"
+        );
+
+        let mut tx = Transaction {
+            raw_data: Some(transaction::Raw {
+                contract: vec![transaction::Contract {
+                    r#type: transaction::contract::ContractType::CreateSmartContract as i32,
+                    parameter: Some(prost_types::Any {
+                        type_url: "type.googleapis.com/protocol.CreateSmartContract".to_string(),
+                        value: CreateSmartContract {
+                            owner_address: owner.into_inner(),
+                            ..Default::default()
+                        }
+                        .encode_to_vec(),
+                    }),
+                    ..Default::default()
+                }],
+                timestamp: 1_700_000_000_000,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let txid = GrpcClient::get_tx_hash(tx.raw_data.as_ref().unwrap());
+        assert_eq!(
+            create_address_from_transaction(&tx).unwrap(),
+            create_address(&owner, &txid)
+        );
+        tx.raw_data.as_mut().unwrap().contract[0].r#type =
+            transaction::contract::ContractType::TriggerSmartContract as i32;
+        assert!(create_address_from_transaction(&tx).is_err());
+    }
+}
@@ -1,29 +1,197 @@
-use secp256k1::Message;
+use std::{fmt, str::FromStr};
 
-pub fn hex2sk(pk: &str) -> Result<secp256k1::SecretKey, String> {
-    let pk = hex::decode(pk)
-        .map_err(|e| e.to_string())?
-        .try_into()
-        .map_err(|_| "private key convert error".to_string())?;
+use secp256k1::{
+    Message, PublicKey, Secp256k1, SecretKey,
+    ecdsa::{RecoverableSignature, RecoveryId},
+    rand,
+};
+use sha3::{Digest, Keccak256};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::{Error, client::Address, utils::typed_data::TypedData};
+
+/// A secp256k1 private key which is zeroized on drop.
+///
+/// `Debug` and `Display` are redacted, the key material is only reachable through
+/// `expose_hex` and `expose_bytes`.
+#[derive(Clone)]
+pub struct PrivateKey([u8; 32]);
+
+impl PrivateKey {
+    pub fn from_bytes(bytes: [u8; 32]) -> Result<Self, Error> {
+        let key = Self(bytes);
+        SecretKey::from_byte_array(key.0).map_err(|e| Error::Key(e.to_string()))?;
+        Ok(key)
+    }
+
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, Error> {
+        let bytes: [u8; 32] = bytes.try_into().map_err(|_| {
+            Error::Key(format!(
+                "invalid private key length: get:{}, expect: 32",
+                bytes.len()
+            ))
+        })?;
+        Self::from_bytes(bytes)
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, Error> {
+        let mut bytes = Zeroizing::new([0u8; 32]);
+        hex::decode_to_slice(hex, bytes.as_mut()).map_err(|e| Error::Key(e.to_string()))?;
+        Self::from_bytes(*bytes)
+    }
+
+    pub fn random() -> Self {
+        SecretKey::new(&mut rand::rng()).into()
+    }
+
+    pub fn expose_hex(&self) -> Zeroizing<String> {
+        Zeroizing::new(hex::encode(self.0))
+    }
+
+    pub fn expose_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    pub fn secret_key(&self) -> SecretKey {
+        SecretKey::from_byte_array(self.0).expect("private key is validated")
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.secret_key().public_key(&Secp256k1::new())
+    }
 
-    secp256k1::SecretKey::from_byte_array(pk).map_err(|e| e.to_string())
+    pub fn address(&self) -> Address {
+        Address::from_public_key(&self.public_key())
+    }
+}
+
+impl From<SecretKey> for PrivateKey {
+    fn from(sk: SecretKey) -> Self {
+        Self(sk.secret_bytes())
+    }
+}
+
+impl FromStr for PrivateKey {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s)
+    }
+}
+
+// compares without short circuit
+impl PartialEq for PrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+    }
+}
+
+impl Eq for PrivateKey {}
+
+impl fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PrivateKey(<redacted>)")
+    }
+}
+
+impl fmt::Display for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<redacted>")
+    }
 }
 
-pub fn sign_tx(tx_hash: Vec<u8>, sk: &secp256k1::SecretKey) -> Result<Vec<u8>, String> {
+impl Drop for PrivateKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for PrivateKey {}
+
+pub fn hex2sk(pk: &str) -> Result<PrivateKey, Error> {
+    PrivateKey::from_hex(pk)
+}
+
+pub fn sign_tx(tx_hash: Vec<u8>, sk: &PrivateKey) -> Result<Vec<u8>, Error> {
     let tx_hash: [u8; 32] = tx_hash
         .try_into()
-        .map_err(|_| "txid convert error".to_string())?;
-    let (rid, sig) = secp256k1::Secp256k1::new()
-        .sign_ecdsa_recoverable(Message::from_digest(tx_hash), sk)
+        .map_err(|_| Error::InvalidArgument("txid convert error".to_string()))?;
+    let (rid, sig) = Secp256k1::new()
+        .sign_ecdsa_recoverable(Message::from_digest(tx_hash), &sk.secret_key())
         .serialize_compact();
     let mut sig = sig.to_vec();
     sig.push(rid as i32 as u8);
     Ok(sig)
 }
 
+/// Recovers the signer address of a 65 bytes signature(r + s + recovery id).
+/// The recovery id may be 0/1 or 27/28.
+pub fn recover_address(hash: &[u8], sig: &[u8]) -> Result<Address, Error> {
+    let hash: [u8; 32] = hash
+        .try_into()
+        .map_err(|_| Error::InvalidArgument("hash convert error".to_string()))?;
+    if sig.len() != 65 {
+        return Err(Error::InvalidArgument(format!(
+            "invalid signature length: get:{}, expect: 65",
+            sig.len()
+        )));
+    }
+    let v = if sig[64] >= 27 { sig[64] - 27 } else { sig[64] };
+    let rid = RecoveryId::try_from(v as i32)
+        .map_err(|e| Error::InvalidArgument(format!("invalid recovery id: {}", e)))?;
+    let sig = RecoverableSignature::from_compact(&sig[..64], rid)
+        .map_err(|e| Error::InvalidArgument(format!("invalid signature: {}", e)))?;
+    let pk = Secp256k1::new()
+        .recover_ecdsa(Message::from_digest(hash), &sig)
+        .map_err(|e| Error::InvalidArgument(format!("recover public key err: {}", e)))?;
+    Ok(Address::from_public_key(&pk))
+}
+
+/// TIP-191 message hash, the same as TronWeb `signMessageV2`:
+/// `keccak256("\x19TRON Signed Message:\n" + len(message) + message)`.
+pub fn hash_message(message: &[u8]) -> [u8; 32] {
+    let mut h = Keccak256::new();
+    h.update(b"\x19TRON Signed Message:\n");
+    h.update(message.len().to_string());
+    h.update(message);
+    h.finalize().into()
+}
+
+/// Recovers the signer of a `signMessageV2` signature, e.g. one produced by TronLink.
+pub fn verify_message(message: &[u8], signature: &[u8]) -> Result<Address, Error> {
+    recover_address(&hash_message(message), signature)
+}
+
+/// Recovers the signer of a TIP-712 typed data signature.
+pub fn verify_typed_data(data: &TypedData, signature: &[u8]) -> Result<Address, Error> {
+    recover_address(&data.hash()?, signature)
+}
+
 #[cfg(test)]
 mod test {
-    use crate::utils::crypto::{hex2sk, sign_tx};
+    use crate::utils::crypto::{
+        PrivateKey, hash_message, hex2sk, recover_address, sign_tx, verify_message,
+    };
+
+    #[test]
+    fn test_private_key() {
+        let hex = "399206ef884e86d46f103a76271a2ec33ebe28a65c600379b36e1d2748412a84";
+        let sk: PrivateKey = hex.parse().expect("parse private key err");
+        assert_eq!(sk.expose_hex().as_str(), hex);
+        assert_eq!(PrivateKey::from_slice(sk.expose_bytes()).unwrap(), sk);
+
+        assert_eq!(format!("{:?}", sk), "PrivateKey(<redacted>)");
+        assert_eq!(sk.to_string(), "<redacted>");
+        assert!(!format!("{:?}", Some(sk.clone())).contains(hex));
+
+        assert!(PrivateKey::from_hex(&hex[2..]).is_err());
+        assert!(PrivateKey::from_hex(&"0".repeat(64)).is_err());
+        assert!(PrivateKey::from_slice(&[1u8; 31]).is_err());
+    }
 
     #[test]
     fn test_sign_tx() {
@@ -36,4 +204,36 @@ mod test {
             "209cc203bf512c8fd1d6c94821a8a128a36f821a72a7f76bd6750f8c9e7acca52a245338383797c5d239af5d70971ce57d0deb2e9f139ca3700263325d36ab6300"
         )
     }
+
+    #[test]
+    fn test_recover_address() {
+        let txid = hex::decode("ef04ae1bba68efc0d43ff95762e202496308b00edd87b70aa2ddde06d0518a97")
+            .unwrap();
+        let sk =
+            hex2sk("399206ef884e86d46f103a76271a2ec33ebe28a65c600379b36e1d2748412a84").unwrap();
+        let expect = sk.address();
+
+        let mut sig = sign_tx(txid.clone(), &sk).unwrap();
+        assert_eq!(recover_address(&txid, &sig).unwrap(), expect);
+
+        sig[64] += 27;
+        assert_eq!(recover_address(&txid, &sig).unwrap(), expect);
+
+        sig[0] ^= 1;
+        assert_ne!(recover_address(&txid, &sig).ok(), Some(expect));
+        assert!(recover_address(&txid, &sig[..64]).is_err());
+    }
+
+    #[test]
+    fn test_verify_message() {
+        assert_eq!(
+            hex::encode(hash_message(b"hello world")),
+            "cf02daeb2bea196ed5692322a66ed50080ce74ff8cb711199f1b04f3c13bc10d"
+        );
+
+        let sk = PrivateKey::random();
+        let sig = sign_tx(hash_message(b"hello world").to_vec(), &sk).unwrap();
+        assert_eq!(verify_message(b"hello world", &sig).unwrap(), sk.address());
+        assert_ne!(verify_message(b"hello", &sig).ok(), Some(sk.address()));
+    }
 }
@@ -1,2 +1,4 @@
 pub mod bs58;
+pub mod contract_address;
 pub mod crypto;
+pub mod typed_data;
@@ -0,0 +1,393 @@
+//! TIP-712 typed structured data, TRON's variant of EIP-712.
+//!
+//! Differences from EIP-712: `address` values may be base58, `41`-prefixed or EVM hex, and are
+//! encoded as 20-byte EVM addresses; `trcToken` is encoded as `uint256`.
+//! https://github.com/tronprotocol/tips/blob/master/tip-712.md
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use num_bigint::{BigInt, Sign};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha3::{Digest, Keccak256};
+
+use crate::{Error, client::Address};
+
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+// field order of the domain type when `types` does not declare it
+const DOMAIN_FIELDS: [(&str, &str); 5] = [
+    ("name", "string"),
+    ("version", "string"),
+    ("chainId", "uint256"),
+    ("verifyingContract", "address"),
+    ("salt", "bytes32"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypedField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// The `eth_signTypedData_v4` JSON document: `types`, `primaryType`, `domain` and `message`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    pub types: BTreeMap<String, Vec<TypedField>>,
+    pub primary_type: String,
+    pub domain: Value,
+    pub message: Value,
+}
+
+impl TypedData {
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json).map_err(|e| Error::Abi(format!("invalid typed data: {}", e)))
+    }
+
+    /// The hash to sign: `keccak256("\x19\x01" + domainSeparator + hashStruct(message))`.
+    pub fn hash(&self) -> Result<[u8; 32], Error> {
+        let mut h = Keccak256::new();
+        h.update([0x19, 0x01]);
+        h.update(self.domain_separator()?);
+        h.update(self.hash_struct(&self.primary_type, &self.message)?);
+        Ok(h.finalize().into())
+    }
+
+    pub fn domain_separator(&self) -> Result<[u8; 32], Error> {
+        self.hash_struct(DOMAIN_TYPE, &self.domain)
+    }
+
+    /// `keccak256(typeHash + encodeData(value))` of a struct type.
+    pub fn hash_struct(&self, ty: &str, value: &Value) -> Result<[u8; 32], Error> {
+        let fields = self.fields(ty)?;
+        let value = value
+            .as_object()
+            .ok_or_else(|| Error::Abi(format!("typed data {} is not an object", ty)))?;
+
+        let mut h = Keccak256::new();
+        h.update(self.type_hash(ty)?);
+        for field in fields.iter() {
+            let v = value.get(&field.name).ok_or_else(|| {
+                Error::Abi(format!("typed data {} missing field: {}", ty, field.name))
+            })?;
+            h.update(self.encode_value(&field.ty, v)?);
+        }
+        Ok(h.finalize().into())
+    }
+
+    pub fn type_hash(&self, ty: &str) -> Result<[u8; 32], Error> {
+        Ok(Keccak256::digest(self.encode_type(ty)?).into())
+    }
+
+    /// e.g. `Mail(Person from,Person to,string contents)Person(string name,address wallet)`,
+    /// referenced struct types are appended in alphabetical order.
+    pub fn encode_type(&self, ty: &str) -> Result<String, Error> {
+        let mut deps = BTreeSet::new();
+        self.collect_deps(ty, &mut deps)?;
+        deps.remove(ty);
+
+        let mut encoded = String::new();
+        for name in std::iter::once(ty).chain(deps.iter().map(String::as_str)) {
+            let fields = self
+                .fields(name)?
+                .iter()
+                .map(|f| format!("{} {}", f.ty, f.name))
+                .collect::<Vec<_>>();
+            encoded.push_str(&format!("{}({})", name, fields.join(",")));
+        }
+        Ok(encoded)
+    }
+
+    fn collect_deps(&self, ty: &str, deps: &mut BTreeSet<String>) -> Result<(), Error> {
+        if !deps.insert(ty.to_string()) {
+            return Ok(());
+        }
+        for field in self.fields(ty)?.iter() {
+            let base = base_type(&field.ty);
+            if self.is_struct(base) {
+                self.collect_deps(base, deps)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn is_struct(&self, ty: &str) -> bool {
+        self.types.contains_key(ty) || ty == DOMAIN_TYPE
+    }
+
+    fn fields(&self, ty: &str) -> Result<Vec<TypedField>, Error> {
+        if let Some(fields) = self.types.get(ty) {
+            return Ok(fields.clone());
+        }
+        if ty != DOMAIN_TYPE {
+            return Err(Error::Abi(format!("unknown typed data type: {}", ty)));
+        }
+        // derived from the fields present in `domain`
+        Ok(DOMAIN_FIELDS
+            .iter()
+            .filter(|(name, _)| self.domain.get(name).is_some_and(|v| !v.is_null()))
+            .map(|(name, ty)| TypedField {
+                name: name.to_string(),
+                ty: ty.to_string(),
+            })
+            .collect())
+    }
+
+    fn encode_value(&self, ty: &str, value: &Value) -> Result<[u8; 32], Error> {
+        if let Some((item, len)) = array_type(ty) {
+            let items = value
+                .as_array()
+                .ok_or_else(|| Error::Abi(format!("typed data {} value is not an array", ty)))?;
+            if len.is_some_and(|len| len != items.len()) {
+                return Err(Error::Abi(format!(
+                    "typed data {} array length mismatch: get:{}",
+                    ty,
+                    items.len()
+                )));
+            }
+            let mut h = Keccak256::new();
+            for v in items {
+                h.update(self.encode_value(item, v)?);
+            }
+            return Ok(h.finalize().into());
+        }
+        if self.is_struct(ty) {
+            return self.hash_struct(ty, value);
+        }
+        encode_atomic(ty, value)
+    }
+}
+
+// `T[]` -> (T, None), `T[n]` -> (T, Some(n))
+fn array_type(ty: &str) -> Option<(&str, Option<usize>)> {
+    let inner = ty.strip_suffix(']')?;
+    let (item, len) = inner.rsplit_once('[')?;
+    Some((item, len.parse().ok()))
+}
+
+fn base_type(ty: &str) -> &str {
+    ty.split('[').next().unwrap_or(ty)
+}
+
+fn encode_atomic(ty: &str, value: &Value) -> Result<[u8; 32], Error> {
+    let invalid = || Error::Abi(format!("invalid typed data {} value: {}", ty, value));
+    let mut word = [0u8; 32];
+    match ty {
+        "string" => {
+            word = Keccak256::digest(value.as_str().ok_or_else(invalid)?).into();
+        }
+        "bytes" => {
+            word = Keccak256::digest(decode_hex(value).ok_or_else(invalid)?).into();
+        }
+        "bool" => {
+            word[31] = value.as_bool().ok_or_else(invalid)? as u8;
+        }
+        "address" => {
+            let address: Address = value
+                .as_str()
+                .ok_or_else(invalid)?
+                .parse()
+                .map_err(|_| invalid())?;
+            word[12..].copy_from_slice(&address.evm_bytes());
+        }
+        "trcToken" => return encode_int(value, 256, false).ok_or_else(invalid),
+        _ => {
+            if let Some(n) = ty.strip_prefix("bytes") {
+                let n: usize = n.parse().map_err(|_| invalid())?;
+                let data = decode_hex(value).ok_or_else(invalid)?;
+                if !(1..=32).contains(&n) || data.len() != n {
+                    return Err(invalid());
+                }
+                word[..n].copy_from_slice(&data);
+            } else if let Some((bits, signed)) = int_bits(ty) {
+                return encode_int(value, bits, signed).ok_or_else(invalid);
+            } else {
+                return Err(Error::Abi(format!("unsupported typed data type: {}", ty)));
+            }
+        }
+    }
+    Ok(word)
+}
+
+// `uint<N>` / `int<N>`, `N` defaults to 256
+fn int_bits(ty: &str) -> Option<(u32, bool)> {
+    let (bits, signed) = match ty.strip_prefix("uint") {
+        Some(bits) => (bits, false),
+        None => (ty.strip_prefix("int")?, true),
+    };
+    let bits = if bits.is_empty() {
+        256
+    } else {
+        bits.parse().ok()?
+    };
+    (bits % 8 == 0 && (8..=256).contains(&bits)).then_some((bits, signed))
+}
+
+// numbers may be JSON numbers or decimal / `0x` hex strings
+fn encode_int(value: &Value, bits: u32, signed: bool) -> Option<[u8; 32]> {
+    let n = match value {
+        Value::Number(n) => n.to_string().parse::<BigInt>().ok()?,
+        Value::String(s) => match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            Some(h) => BigInt::parse_bytes(h.as_bytes(), 16)?,
+            None => s.parse().ok()?,
+        },
+        _ => return None,
+    };
+    let (min, max) = if signed {
+        (
+            -(BigInt::from(1) << (bits - 1)),
+            BigInt::from(1) << (bits - 1),
+        )
+    } else {
+        (BigInt::from(0), BigInt::from(1) << bits)
+    };
+    if n < min || n >= max {
+        return None;
+    }
+
+    let bytes = n.to_signed_bytes_be();
+    let fill = if n.sign() == Sign::Minus { 0xff } else { 0 };
+    let mut word = [fill; 32];
+    // `to_signed_bytes_be` may prepend a zero sign byte to a 256-bit unsigned value
+    let bytes = &bytes[bytes.len().saturating_sub(32)..];
+    word[32 - bytes.len()..].copy_from_slice(bytes);
+    Some(word)
+}
+
+fn decode_hex(value: &Value) -> Option<Vec<u8>> {
+    let s = value.as_str()?;
+    hex::decode(s.strip_prefix("0x").unwrap_or(s)).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{TypedData, encode_atomic};
+    use crate::{
+        client::Address,
+        signer::{LocalSigner, Signer},
+        utils::crypto,
+    };
+
+    // https://github.com/ethereum/EIPs/blob/master/assets/eip-712/Example.js
+    const MAIL: &str = r#"{
+        "types": {
+            "EIP712Domain": [
+                {"name": "name", "type": "string"},
+                {"name": "version", "type": "string"},
+                {"name": "chainId", "type": "uint256"},
+                {"name": "verifyingContract", "type": "address"}
+            ],
+            "Person": [
+                {"name": "name", "type": "string"},
+                {"name": "wallet", "type": "address"}
+            ],
+            "Mail": [
+                {"name": "from", "type": "Person"},
+                {"name": "to", "type": "Person"},
+                {"name": "contents", "type": "string"}
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+            "to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+            "contents": "Hello, Bob!"
+        }
+    }"#;
+
+    #[test]
+    fn test_eip712_vector() {
+        let data = TypedData::from_json(MAIL).unwrap();
+        assert_eq!(
+            data.encode_type("Mail").unwrap(),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            hex::encode(data.domain_separator().unwrap()),
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+        assert_eq!(
+            hex::encode(data.hash_struct("Mail", &data.message).unwrap()),
+            "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+        );
+        assert_eq!(
+            hex::encode(data.hash().unwrap()),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+    }
+
+    #[test]
+    fn test_tron_types() {
+        let expect = TypedData::from_json(MAIL).unwrap();
+
+        // base58 / 41-prefixed addresses and a derived domain type
+        let mut data = expect.clone();
+        data.types.remove("EIP712Domain");
+        data.domain["verifyingContract"] = json!("41cccccccccccccccccccccccccccccccccccccccc");
+        data.message["from"]["wallet"] = json!(
+            "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+                .parse::<Address>()
+                .unwrap()
+                .to_bs58()
+        );
+        assert_eq!(data.hash().unwrap(), expect.hash().unwrap());
+
+        // trcToken is encoded as uint256, but keeps its name in the type hash
+        assert_eq!(
+            encode_atomic("trcToken", &json!("1000001")).unwrap(),
+            encode_atomic("uint256", &json!(1000001)).unwrap()
+        );
+        let data = TypedData::from_json(
+            &json!({
+                "types": {
+                    "Deposit": [
+                        {"name": "tokenId", "type": "trcToken"},
+                        {"name": "amounts", "type": "int64[2]"}
+                    ]
+                },
+                "primaryType": "Deposit",
+                "domain": {"name": "Vault", "chainId": "0x2b6653dc"},
+                "message": {"tokenId": "1000001", "amounts": [-1, "0x10"]}
+            })
+            .to_string(),
+        )
+        .unwrap();
+        assert_eq!(
+            data.encode_type("Deposit").unwrap(),
+            "Deposit(trcToken tokenId,int64[2] amounts)"
+        );
+        assert_eq!(
+            hex::encode(data.hash().unwrap()),
+            "85b85f60394519ab9c5528ab28c8c5412763c11a28fd99bfedd1d9c0f1146f65"
+        );
+
+        let mut data = expect.clone();
+        data.message["contents"] = json!(1);
+        assert!(data.hash().is_err());
+        let mut data = expect;
+        data.domain["chainId"] = json!(-1);
+        assert!(data.hash().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sign_typed_data() {
+        let data = TypedData::from_json(MAIL).unwrap();
+        let signer = LocalSigner::random();
+        let sig = signer.sign_typed_data(&data).await.unwrap();
+        assert!(matches!(sig[64], 27 | 28));
+        assert_eq!(
+            crypto::verify_typed_data(&data, &sig).unwrap(),
+            signer.address()
+        );
+    }
+}
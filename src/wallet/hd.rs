@@ -0,0 +1,253 @@
+use bip39::Mnemonic;
+use hmac::{Hmac, Mac};
+use secp256k1::{PublicKey, Scalar, Secp256k1, rand::RngCore};
+use sha2::Sha512;
+
+use crate::{
+    Error,
+    client::{Address, CreatedAccount},
+    signer::LocalSigner,
+    utils::crypto::PrivateKey,
+};
+
+const HARDENED: u32 = 0x8000_0000;
+
+/// Generates a BIP39 english mnemonic, `word_count` is one of 12, 15, 18, 21 or 24.
+pub fn generate_mnemonic(word_count: usize) -> Result<String, Error> {
+    if !matches!(word_count, 12 | 15 | 18 | 21 | 24) {
+        return Err(Error::InvalidArgument(format!(
+            "invalid mnemonic word count: {}",
+            word_count
+        )));
+    }
+    let mut entropy = [0u8; 32];
+    let len = word_count / 3 * 4;
+    secp256k1::rand::rng().fill_bytes(&mut entropy[..len]);
+    let mnemonic = Mnemonic::from_entropy(&entropy[..len])
+        .map_err(|e| Error::Key(format!("generate mnemonic err: {}", e)))?;
+    Ok(mnemonic.to_string())
+}
+
+pub fn validate_mnemonic(phrase: &str) -> Result<(), Error> {
+    parse_mnemonic(phrase).map(|_| ())
+}
+
+/// BIP39 seed of the mnemonic, `passphrase` is the optional "25th word".
+pub fn mnemonic_to_seed(phrase: &str, passphrase: &str) -> Result<[u8; 64], Error> {
+    Ok(parse_mnemonic(phrase)?.to_seed(passphrase))
+}
+
+fn parse_mnemonic(phrase: &str) -> Result<Mnemonic, Error> {
+    Mnemonic::parse(phrase).map_err(|e| Error::Key(format!("invalid mnemonic: {}", e)))
+}
+
+/// BIP32 extended private key.
+#[derive(Clone)]
+pub struct ExtendedKey {
+    sk: PrivateKey,
+    chain_code: [u8; 32],
+    depth: u8,
+}
+
+impl ExtendedKey {
+    pub fn from_seed(seed: &[u8]) -> Result<Self, Error> {
+        let (sk, chain_code) = hmac_sha512(b"Bitcoin seed", &[seed])?;
+        Ok(Self {
+            sk: PrivateKey::from_bytes(sk)
+                .map_err(|e| Error::Key(format!("invalid master key: {}", e)))?,
+            chain_code,
+            depth: 0,
+        })
+    }
+
+    /// `index` >= 2^31 derives a hardened child.
+    pub fn derive_child(&self, index: u32) -> Result<Self, Error> {
+        let (tweak, chain_code) = if index >= HARDENED {
+            hmac_sha512(
+                &self.chain_code,
+                &[&[0], self.sk.expose_bytes(), &index.to_be_bytes()],
+            )?
+        } else {
+            let pk = PublicKey::from_secret_key(&Secp256k1::new(), &self.sk.secret_key());
+            hmac_sha512(&self.chain_code, &[&pk.serialize(), &index.to_be_bytes()])?
+        };
+        let tweak = Scalar::from_be_bytes(tweak)
+            .map_err(|e| Error::Key(format!("invalid child key(index: {}): {}", index, e)))?;
+        let sk = self
+            .sk
+            .secret_key()
+            .add_tweak(&tweak)
+            .map_err(|e| Error::Key(format!("invalid child key(index: {}): {}", index, e)))?;
+        Ok(Self {
+            sk: sk.into(),
+            chain_code,
+            depth: self.depth.saturating_add(1),
+        })
+    }
+
+    /// Derives a path like `m/44'/195'/0'/0/0`, hardened indexes are marked by `'` or `h`.
+    pub fn derive_path(&self, path: &str) -> Result<Self, Error> {
+        let mut parts = path.split('/');
+        if parts.next() != Some("m") {
+            return Err(Error::InvalidArgument(format!(
+                "invalid derivation path: {}",
+                path
+            )));
+        }
+        parts.try_fold(self.clone(), |key, part| {
+            let (num, hardened) = match part.strip_suffix(['\'', 'h']) {
+                Some(num) => (num, true),
+                None => (part, false),
+            };
+            let index: u32 = num.parse().ok().filter(|i| *i < HARDENED).ok_or_else(|| {
+                Error::InvalidArgument(format!("invalid derivation path: {}", path))
+            })?;
+            key.derive_child(if hardened { index | HARDENED } else { index })
+        })
+    }
+
+    pub fn private_key(&self) -> &PrivateKey {
+        &self.sk
+    }
+
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn address(&self) -> Address {
+        self.sk.address()
+    }
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> Result<([u8; 32], [u8; 32]), Error> {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key)
+        .map_err(|e| Error::Key(format!("hmac key err: {}", e)))?;
+    for d in data {
+        mac.update(d);
+    }
+    let out = mac.finalize().into_bytes();
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&out[..32]);
+    right.copy_from_slice(&out[32..]);
+    Ok((left, right))
+}
+
+/// Deterministic TRON accounts along the BIP44 path `m/44'/195'/account'/0/index`,
+/// the same derivation as TronLink and Ledger.
+#[derive(Clone)]
+pub struct HdWallet {
+    root: ExtendedKey,
+}
+
+impl HdWallet {
+    pub const COIN_TYPE: u32 = 195;
+
+    pub fn from_seed(seed: &[u8]) -> Result<Self, Error> {
+        Ok(Self {
+            root: ExtendedKey::from_seed(seed)?,
+        })
+    }
+
+    pub fn from_mnemonic(phrase: &str, passphrase: &str) -> Result<Self, Error> {
+        Self::from_seed(&mnemonic_to_seed(phrase, passphrase)?)
+    }
+
+    pub fn path(account: u32, index: u32) -> String {
+        format!("m/44'/{}'/{}'/0/{}", Self::COIN_TYPE, account, index)
+    }
+
+    pub fn derive(&self, account: u32, index: u32) -> Result<ExtendedKey, Error> {
+        self.root.derive_path(&Self::path(account, index))
+    }
+
+    pub fn derive_address(&self, account: u32, index: u32) -> Result<Address, Error> {
+        Ok(self.derive(account, index)?.address())
+    }
+
+    pub fn derive_signer(&self, account: u32, index: u32) -> Result<LocalSigner, Error> {
+        Ok(LocalSigner::new(self.derive(account, index)?.sk))
+    }
+
+    pub fn derive_account(&self, account: u32, index: u32) -> Result<CreatedAccount, Error> {
+        let key = self.derive(account, index)?;
+        Ok(CreatedAccount {
+            address_bs58: key.address().to_bs58(),
+            private_key: key.sk,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::signer::Signer;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_mnemonic() {
+        let phrase = generate_mnemonic(24).expect("generate mnemonic err");
+        assert_eq!(phrase.split_whitespace().count(), 24);
+        validate_mnemonic(&phrase).expect("validate mnemonic err");
+
+        assert!(generate_mnemonic(13).is_err());
+        assert!(validate_mnemonic(&PHRASE.replace("about", "abandon")).is_err());
+
+        // https://github.com/trezor/python-mnemonic/blob/master/vectors.json
+        assert_eq!(
+            hex::encode(mnemonic_to_seed(PHRASE, "TREZOR").unwrap()),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+    }
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#test-vector-1
+    #[test]
+    fn test_bip32_vector() {
+        let root =
+            ExtendedKey::from_seed(&hex::decode("000102030405060708090a0b0c0d0e0f").unwrap())
+                .unwrap();
+        assert_eq!(
+            root.private_key().expose_hex().as_str(),
+            "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35"
+        );
+
+        let key = root.derive_path("m/0'/1/2'/2/1000000000").unwrap();
+        assert_eq!(key.depth(), 5);
+        assert_eq!(
+            key.private_key().expose_hex().as_str(),
+            "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8"
+        );
+        assert_eq!(
+            hex::encode(key.chain_code()),
+            "c783e67b921d2beb8f6b389cc646d7263b4145701dadd2161548a8b078e65e9e"
+        );
+
+        assert!(root.derive_path("0/1").is_err());
+        assert!(root.derive_path("m/2147483648").is_err());
+    }
+
+    #[test]
+    fn test_hd_wallet() {
+        let wallet = HdWallet::from_mnemonic(PHRASE, "").unwrap();
+        assert_eq!(HdWallet::path(1, 2), "m/44'/195'/1'/0/2");
+
+        // same as TronWeb `fromMnemonic` with the default path
+        let account = wallet.derive_account(0, 0).unwrap();
+        assert_eq!(account.address_bs58, "TUEZSdKsoDHQMeZwihtdoBiN46zxhGWYdH");
+        let signer = wallet.derive_signer(0, 0).unwrap();
+        assert_eq!(account.address_bs58, signer.address().to_bs58());
+        assert_eq!(
+            account.address_bs58,
+            wallet.derive_address(0, 0).unwrap().to_bs58()
+        );
+        assert_ne!(
+            wallet.derive_address(0, 0).unwrap(),
+            wallet.derive_address(0, 1).unwrap()
+        );
+    }
+}
@@ -0,0 +1,347 @@
+use std::{fs, path::Path};
+
+use aes::cipher::{KeyIvInit, StreamCipher};
+use secp256k1::rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sha3::{Digest, Keccak256};
+use zeroize::Zeroizing;
+
+use crate::{Error, client::Address, utils::crypto::PrivateKey};
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+const CIPHER: &str = "aes-128-ctr";
+
+/// Key derivation function of a keystore.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kdf {
+    /// `n` must be a power of two
+    Scrypt { n: u32, r: u32, p: u32 },
+    /// PBKDF2 with HMAC-SHA256
+    Pbkdf2 { c: u32 },
+}
+
+impl Kdf {
+    /// The scrypt parameters used by most wallets(n = 2^18).
+    pub const STANDARD: Kdf = Kdf::Scrypt {
+        n: 1 << 18,
+        r: 8,
+        p: 1,
+    };
+
+    /// Faster scrypt parameters(n = 2^12) for short-lived or test keys.
+    pub const LIGHT: Kdf = Kdf::Scrypt {
+        n: 1 << 12,
+        r: 8,
+        p: 6,
+    };
+}
+
+/// Web3 Secret Storage v3 keystore, with the `address` field in base58.
+/// https://ethereum.org/en/developers/docs/data-structures-and-encoding/web3-secret-storage/
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    #[serde(default)]
+    pub address: String,
+    #[serde(alias = "Crypto")]
+    pub crypto: CryptoJson,
+    pub id: String,
+    pub version: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CryptoJson {
+    pub cipher: String,
+    pub cipherparams: CipherParams,
+    pub ciphertext: String,
+    #[serde(flatten)]
+    pub kdf: KdfParams,
+    pub mac: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CipherParams {
+    pub iv: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kdf", content = "kdfparams", rename_all = "lowercase")]
+pub enum KdfParams {
+    Scrypt {
+        dklen: usize,
+        n: u32,
+        r: u32,
+        p: u32,
+        salt: String,
+    },
+    Pbkdf2 {
+        dklen: usize,
+        c: u32,
+        prf: String,
+        salt: String,
+    },
+}
+
+impl Keystore {
+    pub fn encrypt(sk: &PrivateKey, password: &str, kdf: Kdf) -> Result<Self, Error> {
+        let mut rng = secp256k1::rand::rng();
+        let mut salt = [0u8; 32];
+        rng.fill_bytes(&mut salt);
+        let mut iv = [0u8; 16];
+        rng.fill_bytes(&mut iv);
+        let mut id = [0u8; 16];
+        rng.fill_bytes(&mut id);
+
+        let kdf = match kdf {
+            Kdf::Scrypt { n, r, p } => KdfParams::Scrypt {
+                dklen: 32,
+                n,
+                r,
+                p,
+                salt: hex::encode(salt),
+            },
+            Kdf::Pbkdf2 { c } => KdfParams::Pbkdf2 {
+                dklen: 32,
+                c,
+                prf: "hmac-sha256".to_string(),
+                salt: hex::encode(salt),
+            },
+        };
+        let key = derive_key(password, &kdf)?;
+
+        let mut ciphertext = *sk.expose_bytes();
+        Aes128Ctr::new(key[..16].into(), &iv.into()).apply_keystream(&mut ciphertext);
+
+        Ok(Self {
+            address: sk.address().to_bs58(),
+            crypto: CryptoJson {
+                cipher: CIPHER.to_string(),
+                cipherparams: CipherParams {
+                    iv: hex::encode(iv),
+                },
+                ciphertext: hex::encode(ciphertext),
+                kdf,
+                mac: hex::encode(mac(&key, &ciphertext)),
+            },
+            id: uuid_v4(id),
+            version: 3,
+        })
+    }
+
+    /// Decrypts the secret key, checking the MAC and the `address` field.
+    pub fn decrypt(&self, password: &str) -> Result<PrivateKey, Error> {
+        if self.version != 3 {
+            return Err(Error::Key(format!(
+                "unsupported keystore version: {}",
+                self.version
+            )));
+        }
+        if self.crypto.cipher != CIPHER {
+            return Err(Error::Key(format!(
+                "unsupported keystore cipher: {}",
+                self.crypto.cipher
+            )));
+        }
+
+        let ciphertext = decode_hex("ciphertext", &self.crypto.ciphertext)?;
+        let key = derive_key(password, &self.crypto.kdf)?;
+        if mac(&key, &ciphertext).as_slice() != decode_hex("mac", &self.crypto.mac)? {
+            return Err(Error::Key(
+                "keystore mac mismatch, wrong password?".to_string(),
+            ));
+        }
+
+        let iv: [u8; 16] = decode_hex("iv", &self.crypto.cipherparams.iv)?
+            .try_into()
+            .map_err(|_| Error::Key("invalid keystore iv length".to_string()))?;
+        let mut plaintext = Zeroizing::new(ciphertext);
+        Aes128Ctr::new(key[..16].into(), &iv.into()).apply_keystream(&mut plaintext);
+        let sk = PrivateKey::from_slice(&plaintext)
+            .map_err(|e| Error::Key(format!("invalid keystore secret key: {}", e)))?;
+
+        if !self.address.is_empty() {
+            let address: Address = self.address.parse()?;
+            if address != sk.address() {
+                return Err(Error::Key(format!(
+                    "keystore address mismatch: {}",
+                    self.address
+                )));
+            }
+        }
+        Ok(sk)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json).map_err(|e| Error::Key(format!("invalid keystore json: {}", e)))
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| Error::Key(format!("encode keystore json err: {}", e)))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let json = fs::read_to_string(path)
+            .map_err(|e| Error::Key(format!("read keystore err: {}", e)))?;
+        Self::from_json(&json)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        fs::write(path, self.to_json()?)
+            .map_err(|e| Error::Key(format!("write keystore err: {}", e)))
+    }
+}
+
+fn derive_key(password: &str, kdf: &KdfParams) -> Result<Zeroizing<[u8; 32]>, Error> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    match kdf {
+        KdfParams::Scrypt {
+            dklen,
+            n,
+            r,
+            p,
+            salt,
+        } => {
+            check_dklen(*dklen)?;
+            if !n.is_power_of_two() {
+                return Err(Error::Key(format!("invalid scrypt n: {}", n)));
+            }
+            let params = scrypt::Params::new(n.trailing_zeros() as u8, *r, *p, 32)
+                .map_err(|e| Error::Key(format!("invalid scrypt params: {}", e)))?;
+            scrypt::scrypt(
+                password.as_bytes(),
+                &decode_hex("salt", salt)?,
+                &params,
+                key.as_mut(),
+            )
+            .map_err(|e| Error::Key(format!("scrypt err: {}", e)))?;
+        }
+        KdfParams::Pbkdf2 {
+            dklen,
+            c,
+            prf,
+            salt,
+        } => {
+            check_dklen(*dklen)?;
+            if prf != "hmac-sha256" {
+                return Err(Error::Key(format!("unsupported pbkdf2 prf: {}", prf)));
+            }
+            pbkdf2::pbkdf2_hmac::<Sha256>(
+                password.as_bytes(),
+                &decode_hex("salt", salt)?,
+                *c,
+                key.as_mut(),
+            );
+        }
+    }
+    Ok(key)
+}
+
+fn check_dklen(dklen: usize) -> Result<(), Error> {
+    if dklen != 32 {
+        return Err(Error::Key(format!("unsupported keystore dklen: {}", dklen)));
+    }
+    Ok(())
+}
+
+// mac = keccak256(<derived key[16..32]> + <ciphertext>)
+fn mac(key: &[u8; 32], ciphertext: &[u8]) -> [u8; 32] {
+    let mut h = Keccak256::new();
+    h.update(&key[16..]);
+    h.update(ciphertext);
+    h.finalize().into()
+}
+
+fn decode_hex(field: &str, h: &str) -> Result<Vec<u8>, Error> {
+    hex::decode(h).map_err(|e| Error::Key(format!("invalid keystore {}: {}", field, e)))
+}
+
+fn uuid_v4(mut b: [u8; 16]) -> String {
+    b[6] = (b[6] & 0x0f) | 0x40;
+    b[8] = (b[8] & 0x3f) | 0x80;
+    let h = hex::encode(b);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &h[..8],
+        &h[8..12],
+        &h[12..16],
+        &h[16..20],
+        &h[20..]
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::crypto::hex2sk;
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let sk = PrivateKey::random();
+        for kdf in [Kdf::LIGHT, Kdf::Pbkdf2 { c: 1024 }] {
+            let ks = Keystore::encrypt(&sk, "password", kdf).expect("encrypt err");
+            assert_eq!(ks.address, sk.address().to_bs58());
+
+            let ks = Keystore::from_json(&ks.to_json().unwrap()).expect("parse json err");
+            assert_eq!(ks.decrypt("password").expect("decrypt err"), sk);
+            assert!(matches!(ks.decrypt("wrong"), Err(Error::Key(_))));
+        }
+    }
+
+    // https://ethereum.org/en/developers/docs/data-structures-and-encoding/web3-secret-storage/#test-vectors
+    #[test]
+    fn test_decrypt_vectors() {
+        let expect =
+            hex2sk("7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d").unwrap();
+
+        let pbkdf2 = r#"{
+            "crypto": {
+                "cipher": "aes-128-ctr",
+                "cipherparams": {"iv": "6087dab2f9fdbbfaddc31a909735c1e6"},
+                "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+                "kdf": "pbkdf2",
+                "kdfparams": {
+                    "c": 262144,
+                    "dklen": 32,
+                    "prf": "hmac-sha256",
+                    "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+                },
+                "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+            },
+            "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+            "version": 3
+        }"#;
+        let ks = Keystore::from_json(pbkdf2).unwrap();
+        assert_eq!(ks.decrypt("testpassword").unwrap(), expect);
+
+        // generated with python hashlib.scrypt and cryptography AES-CTR
+        let scrypt = r#"{
+            "address": "0x008aeeda4d805471df9b2a5b0f38a0c3bcba786b",
+            "crypto": {
+                "cipher": "aes-128-ctr",
+                "cipherparams": {"iv": "83dbcc02d8ccb40e466191a123791e0e"},
+                "ciphertext": "3b4309355ad643f2b15cfb6a83a7f6f328e7a6459a56ab8c6e25a89c8f43eb80",
+                "kdf": "scrypt",
+                "kdfparams": {
+                    "dklen": 32,
+                    "n": 4096,
+                    "p": 1,
+                    "r": 8,
+                    "salt": "ab0c7876052600dd703518d6fc3fe8984592145b591fc8fb5c6d43190334ba19"
+                },
+                "mac": "994d83f6bfb7e6e3aa95980f72b6ad87db9d352789d0f2e433cf777425db3a42"
+            },
+            "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+            "version": 3
+        }"#;
+        let ks = Keystore::from_json(scrypt).unwrap();
+        assert_eq!(ks.decrypt("testpassword").unwrap(), expect);
+    }
+
+    #[test]
+    fn test_uuid_v4() {
+        let id = uuid_v4([0xff; 16]);
+        assert_eq!(id, "ffffffff-ffff-4fff-bfff-ffffffffffff");
+    }
+}
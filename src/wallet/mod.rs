@@ -0,0 +1,8 @@
+mod hd;
+pub use hd::{ExtendedKey, HdWallet, generate_mnemonic, mnemonic_to_seed, validate_mnemonic};
+
+mod keystore;
+pub use keystore::{CipherParams, CryptoJson, Kdf, KdfParams, Keystore};
+
+mod vanity;
+pub use vanity::{Create2Match, VanityMatch, VanityProgress, VanitySearch};
@@ -0,0 +1,459 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use num_bigint::BigUint;
+use secp256k1::{
+    Secp256k1, SecretKey,
+    rand::{self, RngCore},
+};
+
+use crate::{
+    Error,
+    client::Address,
+    utils::{contract_address::create2_address, crypto::PrivateKey},
+};
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+// every base58 address is 34 characters: `0x41` + 20 bytes + 4 bytes of checksum
+const ADDRESS_LEN: usize = 34;
+// longer prefixes are out of reach anyway, and bound the case variants to enumerate
+const MAX_PREFIX_LEN: usize = 16;
+
+/// Multi-threaded search for an address matching a base58 prefix and/or suffix, of a new
+/// key(`search`) or of a `CREATE2` deployment(`search_create2`).
+///
+/// The prefix is matched after the leading `T`, e.g. `prefix("RX")` finds `TRX...`.
+/// Keys come from the system RNG, like `PrivateKey::random`.
+#[derive(Debug, Clone)]
+pub struct VanitySearch {
+    prefix: String,
+    suffix: String,
+    case_insensitive: bool,
+    threads: usize,
+    max_attempts: Option<u64>,
+}
+
+impl Default for VanitySearch {
+    fn default() -> Self {
+        Self {
+            prefix: String::new(),
+            suffix: String::new(),
+            case_insensitive: false,
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+            max_attempts: None,
+        }
+    }
+}
+
+impl VanitySearch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    pub fn suffix(mut self, suffix: &str) -> Self {
+        self.suffix = suffix.to_string();
+        self
+    }
+
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Defaults to the available parallelism.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Gives up after `max_attempts` keys, unlimited by default.
+    pub fn max_attempts(mut self, max_attempts: u64) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Checks that the pattern can match a base58 address.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.probability().map(|_| ())
+    }
+
+    /// Probability that one random address matches the pattern.
+    pub fn probability(&self) -> Result<f64, Error> {
+        if self.prefix.is_empty() && self.suffix.is_empty() {
+            return Err(Error::InvalidArgument(
+                "vanity prefix and suffix are empty".to_string(),
+            ));
+        }
+        if self.prefix.len() > MAX_PREFIX_LEN
+            || 1 + self.prefix.len() + self.suffix.len() > ADDRESS_LEN
+        {
+            return Err(Error::InvalidArgument(
+                "vanity pattern is too long".to_string(),
+            ));
+        }
+        let prefix = self.variants(&self.prefix)?;
+        let suffix = self.variants(&self.suffix)?;
+
+        let mut covered = BigUint::ZERO;
+        let mut pattern = vec![b'T'];
+        prefix_coverage(&prefix, &mut pattern, &mut covered);
+        if covered == BigUint::ZERO {
+            return Err(Error::InvalidArgument(format!(
+                "no base58 address starts with T{}",
+                self.prefix
+            )));
+        }
+        let mut probability = to_f64(&covered) / 2f64.powi(192);
+        for chars in suffix {
+            probability *= chars.len() as f64 / ALPHABET.len() as f64;
+        }
+        Ok(probability)
+    }
+
+    /// Expected number of keys to generate until a match.
+    pub fn expected_attempts(&self) -> Result<f64, Error> {
+        Ok(1.0 / self.probability()?)
+    }
+
+    pub fn matches(&self, address: &str) -> bool {
+        let (p, s) = (self.prefix.len(), self.suffix.len());
+        if address.len() < 1 + p + s {
+            return false;
+        }
+        let (Some(head), Some(tail)) = (address.get(1..1 + p), address.get(address.len() - s..))
+        else {
+            return false;
+        };
+        if self.case_insensitive {
+            head.eq_ignore_ascii_case(&self.prefix) && tail.eq_ignore_ascii_case(&self.suffix)
+        } else {
+            head == self.prefix && tail == self.suffix
+        }
+    }
+
+    /// Runs the search on `threads` threads, blocking until a match is found. Returns
+    /// `None` when `max_attempts` is reached first.
+    pub fn search(&self) -> Result<Option<VanityMatch>, Error> {
+        self.search_with_progress(Duration::MAX, |_| {})
+    }
+
+    /// Like `search`, calling `progress` every `interval`.
+    pub fn search_with_progress(
+        &self,
+        interval: Duration,
+        progress: impl FnMut(&VanityProgress),
+    ) -> Result<Option<VanityMatch>, Error> {
+        let found = self.run(interval, progress, || {
+            let secp = Secp256k1::new();
+            let mut rng = rand::rng();
+            move || {
+                let sk = SecretKey::new(&mut rng);
+                (Address::from_public_key(&sk.public_key(&secp)), sk)
+            }
+        })?;
+        Ok(found.map(|(address, sk, attempts, elapsed)| VanityMatch {
+            private_key: sk.into(),
+            address,
+            attempts,
+            elapsed,
+        }))
+    }
+
+    /// Mines a salt for which `create2_address(deployer, salt, init_code_hash)` matches.
+    /// Returns `None` when `max_attempts` is reached first.
+    pub fn search_create2(
+        &self,
+        deployer: &Address,
+        init_code_hash: &[u8; 32],
+    ) -> Result<Option<Create2Match>, Error> {
+        self.search_create2_with_progress(deployer, init_code_hash, Duration::MAX, |_| {})
+    }
+
+    /// Like `search_create2`, calling `progress` every `interval`.
+    pub fn search_create2_with_progress(
+        &self,
+        deployer: &Address,
+        init_code_hash: &[u8; 32],
+        interval: Duration,
+        progress: impl FnMut(&VanityProgress),
+    ) -> Result<Option<Create2Match>, Error> {
+        let found = self.run(interval, progress, || {
+            // a random start per thread, then counting up in the low bytes
+            let mut salt = [0u8; 32];
+            rand::rng().fill_bytes(&mut salt);
+            let mut counter = 0u64;
+            move || {
+                counter += 1;
+                salt[24..].copy_from_slice(&counter.to_be_bytes());
+                (create2_address(deployer, &salt, init_code_hash), salt)
+            }
+        })?;
+        Ok(
+            found.map(|(address, salt, attempts, elapsed)| Create2Match {
+                salt,
+                address,
+                attempts,
+                elapsed,
+            }),
+        )
+    }
+
+    // Runs `threads` threads which try the candidates of their own `generator` until one
+    // matches.
+    fn run<T: Send, G: FnMut() -> (Address, T)>(
+        &self,
+        interval: Duration,
+        mut progress: impl FnMut(&VanityProgress),
+        generator: impl Fn() -> G + Sync,
+    ) -> Result<Option<(Address, T, u64, Duration)>, Error> {
+        let expected_attempts = self.expected_attempts()?;
+        let start = Instant::now();
+        let attempts = Arc::new(AtomicU64::new(0));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for _ in 0..self.threads {
+                let (tx, attempts, stop) = (tx.clone(), attempts.clone(), stop.clone());
+                let generator = &generator;
+                scope.spawn(move || {
+                    let mut next = generator();
+                    while !stop.load(Ordering::Relaxed) {
+                        let n = attempts.fetch_add(1, Ordering::Relaxed) + 1;
+                        if self.max_attempts.is_some_and(|max| n > max) {
+                            break;
+                        }
+                        let (address, value) = next();
+                        if self.matches(&address.to_bs58()) {
+                            let _ = tx.send((address, value, n, start.elapsed()));
+                            break;
+                        }
+                    }
+                });
+            }
+            drop(tx);
+
+            let found = loop {
+                match rx.recv_timeout(interval) {
+                    Ok(found) => break Some(found),
+                    Err(RecvTimeoutError::Disconnected) => break None,
+                    Err(RecvTimeoutError::Timeout) => progress(&VanityProgress {
+                        attempts: attempts.load(Ordering::Relaxed),
+                        elapsed: start.elapsed(),
+                        expected_attempts,
+                    }),
+                }
+            };
+            stop.store(true, Ordering::Relaxed);
+            Ok(found)
+        })
+    }
+
+    // the base58 characters each pattern character may match
+    fn variants(&self, pattern: &str) -> Result<Vec<Vec<u8>>, Error> {
+        pattern
+            .bytes()
+            .map(|c| {
+                let chars = ALPHABET
+                    .iter()
+                    .copied()
+                    .filter(|a| *a == c || (self.case_insensitive && a.eq_ignore_ascii_case(&c)))
+                    .collect::<Vec<_>>();
+                if chars.is_empty() {
+                    return Err(Error::InvalidArgument(format!(
+                        "{:?} is not a base58 character",
+                        c as char
+                    )));
+                }
+                Ok(chars)
+            })
+            .collect()
+    }
+}
+
+// Adds the number of addresses starting with each case variant of `pattern` + `rest`,
+// skipping variants which are already out of the address range.
+fn prefix_coverage(rest: &[Vec<u8>], pattern: &mut Vec<u8>, covered: &mut BigUint) {
+    let n = coverage(pattern);
+    match rest.split_first() {
+        _ if n == BigUint::ZERO => {}
+        None => *covered += n,
+        Some((chars, rest)) => {
+            for c in chars {
+                pattern.push(*c);
+                prefix_coverage(rest, pattern, covered);
+                pattern.pop();
+            }
+        }
+    }
+}
+
+// number of addresses starting with `pattern`
+fn coverage(pattern: &[u8]) -> BigUint {
+    let base = BigUint::from(ALPHABET.len());
+    let value = pattern.iter().fold(BigUint::ZERO, |n, c| {
+        n * &base
+            + ALPHABET
+                .iter()
+                .position(|a| a == c)
+                .expect("base58 character")
+    });
+    let width = base.pow((ADDRESS_LEN - pattern.len()) as u32);
+    let lo = value * &width;
+    let hi = &lo + width;
+
+    let min = BigUint::from(Address::PREFIX) << 192;
+    let max = BigUint::from(Address::PREFIX + 1) << 192;
+    let (lo, hi) = (lo.max(min), hi.min(max));
+    if hi > lo { hi - lo } else { BigUint::ZERO }
+}
+
+fn to_f64(n: &BigUint) -> f64 {
+    n.to_u64_digits()
+        .iter()
+        .rev()
+        .fold(0.0, |f, d| f * 2f64.powi(64) + *d as f64)
+}
+
+/// The key found by `VanitySearch`.
+#[derive(Debug, Clone)]
+pub struct VanityMatch {
+    pub private_key: PrivateKey,
+    pub address: Address,
+    /// Keys generated by all threads until the match.
+    pub attempts: u64,
+    pub elapsed: Duration,
+}
+
+/// The salt found by `VanitySearch::search_create2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Create2Match {
+    pub salt: [u8; 32],
+    pub address: Address,
+    /// Salts tried by all threads until the match.
+    pub attempts: u64,
+    pub elapsed: Duration,
+}
+
+/// Progress reported by `VanitySearch::search_with_progress`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VanityProgress {
+    pub attempts: u64,
+    pub elapsed: Duration,
+    pub expected_attempts: f64,
+}
+
+impl VanityProgress {
+    /// Keys per second.
+    pub fn rate(&self) -> f64 {
+        self.attempts as f64 / self.elapsed.as_secs_f64()
+    }
+
+    /// Probability that a match would have been found by now.
+    pub fn probability(&self) -> f64 {
+        -(self.attempts as f64 * (-1.0 / self.expected_attempts).ln_1p()).exp_m1()
+    }
+
+    /// Expected remaining time at the current rate. Every key is an independent try, so
+    /// this does not shrink with the attempts already made.
+    pub fn eta(&self) -> Option<Duration> {
+        let rate = self.rate();
+        (rate.is_finite() && rate > 0.0)
+            .then(|| Duration::try_from_secs_f64(self.expected_attempts / rate).ok())
+            .flatten()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::VanitySearch;
+    use crate::utils::contract_address::{create2_address, init_code_hash};
+
+    #[test]
+    fn test_vanity_pattern() {
+        let search = VanitySearch::new().prefix("RX");
+        assert!((search.expected_attempts().unwrap() - 1353.6).abs() < 0.1);
+        assert!(search.matches("TRXqVYbvCEfRcGZVrYLqKpzYtxYzqjr4Rk"));
+        assert!(!search.matches("TRxqVYbvCEfRcGZVrYLqKpzYtxYzqjr4Rk"));
+        assert!(
+            search
+                .case_insensitive(true)
+                .matches("TRxqVYbvCEfRcGZVrYLqKpzYtxYzqjr4Rk")
+        );
+
+        // the second character ranges from `9` to `Z`, up to `TZJozAg...`
+        assert!(VanitySearch::new().prefix("9").validate().is_ok());
+        assert!(VanitySearch::new().prefix("ZJ").validate().is_ok());
+        assert!(VanitySearch::new().prefix("ZK").validate().is_err());
+        assert!(VanitySearch::new().prefix("8").validate().is_err());
+        assert!(VanitySearch::new().prefix("r").validate().is_err());
+        assert!(
+            VanitySearch::new()
+                .prefix("r")
+                .case_insensitive(true)
+                .validate()
+                .is_ok()
+        );
+        // `0`, `O`, `I` and `l` are not base58
+        assert!(VanitySearch::new().suffix("l").validate().is_err());
+        assert!(
+            VanitySearch::new()
+                .suffix("l")
+                .case_insensitive(true)
+                .validate()
+                .is_ok()
+        );
+        assert!(VanitySearch::new().suffix("0").validate().is_err());
+        assert!(VanitySearch::new().validate().is_err());
+    }
+
+    #[test]
+    fn test_vanity_search() {
+        let search = VanitySearch::new().prefix("R").suffix("a").threads(2);
+        let found = search
+            .search_with_progress(Duration::from_millis(1), |p| {
+                assert!(p.expected_attempts > 1000.0 && p.probability() < 1.0);
+            })
+            .unwrap()
+            .expect("no match");
+        let address = found.address.to_bs58();
+        assert!(address.starts_with("TR") && address.ends_with('a'));
+        assert_eq!(found.private_key.address(), found.address);
+
+        let none = VanitySearch::new()
+            .prefix("ZJozAg")
+            .max_attempts(10)
+            .search()
+            .unwrap();
+        assert!(none.is_none());
+
+        let deployer = found.address;
+        let code_hash = init_code_hash(&[0x60, 0x80]);
+        let found = VanitySearch::new()
+            .suffix("ab")
+            .case_insensitive(true)
+            .threads(2)
+            .search_create2(&deployer, &code_hash)
+            .unwrap()
+            .expect("no match");
+        assert!(found.address.to_bs58().to_lowercase().ends_with("ab"));
+        assert_eq!(
+            create2_address(&deployer, &found.salt, &code_hash),
+            found.address
+        );
+    }
+}